@@ -1,6 +1,7 @@
+use crate::expression::ExprBuilder;
 use polars::prelude::*;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum AggFunc {
     Count,
     Sum,
@@ -8,6 +9,77 @@ pub enum AggFunc {
     Median,
     Min,
     Max,
+    Std,
+    Var,
+    First,
+    Last,
+    NUnique,
+    Quantile,
+}
+
+impl AggFunc {
+    pub const ALL: [AggFunc; 12] = [
+        AggFunc::Count,
+        AggFunc::Sum,
+        AggFunc::Mean,
+        AggFunc::Median,
+        AggFunc::Min,
+        AggFunc::Max,
+        AggFunc::Std,
+        AggFunc::Var,
+        AggFunc::First,
+        AggFunc::Last,
+        AggFunc::NUnique,
+        AggFunc::Quantile,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AggFunc::Count => "count",
+            AggFunc::Sum => "sum",
+            AggFunc::Mean => "mean",
+            AggFunc::Median => "median",
+            AggFunc::Min => "min",
+            AggFunc::Max => "max",
+            AggFunc::Std => "std",
+            AggFunc::Var => "var",
+            AggFunc::First => "first",
+            AggFunc::Last => "last",
+            AggFunc::NUnique => "n_unique",
+            AggFunc::Quantile => "quantile",
+        }
+    }
+
+    /// Suffix of the results when several functions are applied, e.g. `p95` for the 95th
+    /// percentile.
+    pub fn suffix(&self, percentile: f64) -> String {
+        match self {
+            AggFunc::Quantile => format!("p{}", percentile),
+            _ => self.label().to_string(),
+        }
+    }
+
+    /// The aggregation of an expression, e.g. the sum of the selected columns.
+    /// `percentile`, from 0 to 100, is the one of the Quantile function.
+    pub fn apply(&self, expr: Expr, percentile: f64) -> Expr {
+        match self {
+            AggFunc::Count => expr.count(),
+            AggFunc::Sum => expr.sum(),
+            AggFunc::Mean => expr.mean(),
+            AggFunc::Median => expr.median(),
+            AggFunc::Min => expr.min(),
+            AggFunc::Max => expr.max(),
+            // sample standard deviation and variance, like pandas.
+            AggFunc::Std => expr.std(1),
+            AggFunc::Var => expr.var(1),
+            AggFunc::First => expr.first(),
+            AggFunc::Last => expr.last(),
+            AggFunc::NUnique => expr.n_unique(),
+            AggFunc::Quantile => {
+                expr.quantile(lit(percentile / 100.0), QuantileInterpolOptions::Linear)
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -16,9 +88,17 @@ pub struct DataFrameAggregate {
     pub agg_selection: String,
     pub groupby: Vec<String>,
     pub aggcols: Vec<String>,
-    pub aggfunc: AggFunc,
-    pub aggdata: Option<DataFrame>,
-    pub display: bool,
+    /// Functions applied to each column. With more than one, the results are suffixed with
+    /// the function, e.g. `price_sum` and `price_mean`.
+    pub aggfuncs: Vec<AggFunc>,
+    /// Percentile of the Quantile function.
+    pub percentile: f64,
+    pub inplace: bool,
+    /// Also aggregate an expression, named `expr_name`.
+    pub use_expr: bool,
+    pub expr_name: String,
+    pub builder: ExprBuilder,
+    pub error: Option<String>,
 }
 
 impl Default for DataFrameAggregate {
@@ -28,9 +108,13 @@ impl Default for DataFrameAggregate {
             agg_selection: String::default(),
             groupby: Vec::new(),
             aggcols: Vec::new(),
-            aggfunc: AggFunc::Count,
-            aggdata: None,
-            display: false,
+            aggfuncs: vec![AggFunc::Count],
+            percentile: 95.0,
+            inplace: false,
+            use_expr: false,
+            expr_name: String::from("expr"),
+            builder: ExprBuilder::default(),
+            error: None,
         }
     }
 }
@@ -0,0 +1,180 @@
+use hmac::{Hmac, Mac};
+use polars::prelude::*;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnonymizeMode {
+    Hash,
+    Mask,
+    Token,
+}
+
+impl AnonymizeMode {
+    pub const ALL: [AnonymizeMode; 3] = [
+        AnonymizeMode::Hash,
+        AnonymizeMode::Mask,
+        AnonymizeMode::Token,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AnonymizeMode::Hash => "Salted hash",
+            AnonymizeMode::Mask => "Mask",
+            AnonymizeMode::Token => "Random tokens",
+        }
+    }
+}
+
+/// Replaces the values of a column so that a frame can be shared without them: by an
+/// HMAC-SHA256 of the value keyed with the salt, by `*` except for the last characters, or by
+/// tokens like `email_12` in a random order. Equal values get equal replacements, so the
+/// column can still be grouped or joined on, and nulls stay null.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameAnonymize {
+    pub column: String,
+    pub mode: AnonymizeMode,
+    pub salt: String,
+    /// Characters left visible at the end of masked values, e.g. the last digits of a card.
+    pub keep: usize,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameAnonymize {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            mode: AnonymizeMode::Hash,
+            salt: String::new(),
+            keep: 0,
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+/// The first 64 bits of the HMAC-SHA256 of the value with the salt as the key, in hex. It's
+/// the same on every machine and version, so frames anonymized apart can still be joined.
+fn keyed_hash(salt: &str, value: &str) -> String {
+    // HMAC takes keys of any length, an empty salt included.
+    let mut mac = Hmac::<Sha256>::new_from_slice(salt.as_bytes()).expect("any key length");
+    mac.update(value.as_bytes());
+    mac.finalize().into_bytes()[..8]
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn hash_value(key: impl Hash, value: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl DataFrameAnonymize {
+    /// `seed` orders the tokens, the other modes don't depend on it.
+    pub fn apply(&self, df: LazyFrame, seed: u64) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to anonymize");
+        }
+        let mut df = df.collect()?;
+        // numbers and dates are anonymized as text.
+        let values = df.column(&self.column)?.cast(&DataType::String)?;
+        let values = values.str()?;
+        let anonymized: StringChunked = match self.mode {
+            AnonymizeMode::Hash => values
+                .into_iter()
+                .map(|v| v.map(|v| keyed_hash(&self.salt, v)))
+                .collect(),
+            AnonymizeMode::Mask => values
+                .into_iter()
+                .map(|v| {
+                    v.map(|v| {
+                        let length = v.chars().count();
+                        let hidden = length.saturating_sub(self.keep);
+                        let visible: String = v.chars().skip(hidden).collect();
+                        format!("{}{}", "*".repeat(hidden), visible)
+                    })
+                })
+                .collect(),
+            AnonymizeMode::Token => {
+                let mut distinct: Vec<&str> = values.into_iter().flatten().collect();
+                distinct.sort_unstable();
+                distinct.dedup();
+                distinct.sort_by_key(|v| hash_value(seed, v));
+                let tokens: HashMap<&str, String> = distinct
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, v)| (v, format!("{}_{}", self.column, i + 1)))
+                    .collect();
+                values
+                    .into_iter()
+                    .map(|v| v.map(|v| tokens[v].as_str()))
+                    .collect()
+            }
+        };
+        df.with_column(anonymized.into_series().with_name(&self.column))?;
+        Ok(df)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anonymize(mode: AnonymizeMode, salt: &str) -> Series {
+        let df =
+            df! { "email" => [Some("a@x.com"), None, Some("b@x.com"), Some("a@x.com")] }.unwrap();
+        let anonymize = DataFrameAnonymize {
+            column: String::from("email"),
+            mode,
+            salt: String::from(salt),
+            keep: 3,
+            ..Default::default()
+        };
+        let df = anonymize.apply(df.lazy(), 7).unwrap();
+        df.column("email").unwrap().clone()
+    }
+
+    #[test]
+    fn hash_is_pinned() {
+        // the HMAC-SHA256 of "a@x.com" keyed with "pepper", which must never change.
+        assert_eq!(keyed_hash("pepper", "a@x.com"), "4c4becb0c2ddc124");
+        let hashed = anonymize(AnonymizeMode::Hash, "pepper");
+        let hashed: Vec<_> = hashed.str().unwrap().into_iter().collect();
+        assert_eq!(hashed[0], Some(keyed_hash("pepper", "a@x.com").as_str()));
+        assert_eq!(hashed[1], None);
+        assert_eq!(hashed[0], hashed[3]);
+        assert_ne!(hashed[0], hashed[2]);
+        assert_ne!(
+            keyed_hash("salt", "a@x.com"),
+            keyed_hash("pepper", "a@x.com")
+        );
+    }
+
+    #[test]
+    fn mask_keeps_the_last_characters() {
+        let masked = anonymize(AnonymizeMode::Mask, "");
+        assert_eq!(
+            masked,
+            Series::new(
+                "email",
+                [Some("****com"), None, Some("****com"), Some("****com")]
+            )
+        );
+    }
+
+    #[test]
+    fn equal_values_get_equal_tokens() {
+        let tokens = anonymize(AnonymizeMode::Token, "");
+        let tokens: Vec<_> = tokens.str().unwrap().into_iter().collect();
+        assert_eq!(tokens[0], tokens[3]);
+        assert_eq!(tokens[1], None);
+        let mut distinct = vec![tokens[0].unwrap(), tokens[2].unwrap()];
+        distinct.sort_unstable();
+        assert_eq!(distinct, ["email_1", "email_2"]);
+    }
+}
@@ -1,10 +1,37 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::background::{self, BackgroundImport, BackgroundTask};
 use crate::container::*;
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "postgres", feature = "mysql", feature = "sqlite")
+))]
+use crate::database;
+use crate::editor::DataFrameEditor;
+#[cfg(feature = "cloud")]
+use crate::import::CloudImportDialog;
+#[cfg(any(feature = "postgres", feature = "mysql"))]
+use crate::import::DatabaseImportDialog;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::import::FolderImportDialog;
+#[cfg(feature = "sqlite")]
+use crate::import::SqliteBrowser;
+use crate::import::{CsvImportDialog, PasteImportDialog, UrlImportDialog};
+use crate::reader::{self, FileFormat, ImportSource};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::reader::{CsvImportOptions, FileSource, RecentFile};
+use crate::sample::SampleDataDialog;
+use crate::sql::{self, SqlDialog};
+use crate::table::TableLayout;
+use crate::utils::get_container;
 use polars::prelude::*;
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
 use std::rc::Rc;
 
 #[cfg(target_arch = "wasm32")]
@@ -12,6 +39,9 @@ use rfd::AsyncFileDialog;
 #[cfg(target_arch = "wasm32")]
 use std::future::Future;
 
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_RECENT_FILES: usize = 10;
+
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
 #[derive(serde::Deserialize, serde::Serialize, Debug, Clone)]
 #[serde(default)] // if we add new fields, give them default values when deserializing old state
@@ -24,6 +54,42 @@ pub struct App {
     frames: Rc<RefCell<Vec<HashMap<String, DataFrameContainer>>>>,
     titles: Rc<RefCell<Vec<String>>>,
     df_cols: Rc<RefCell<HashMap<String, Vec<String>>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    recent_files: Vec<RecentFile>,
+    /// Scan CSV and Parquet files lazily instead of reading them into memory.
+    #[cfg(not(target_arch = "wasm32"))]
+    lazy_scan: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    imports: Rc<RefCell<Vec<BackgroundImport>>>,
+    /// Data view layouts by container title.
+    layouts: HashMap<String, TableLayout>,
+    /// Query results so far, numbering their titles.
+    queries: usize,
+    #[serde(skip)]
+    csv_import: Rc<RefCell<Option<CsvImportDialog>>>,
+    #[serde(skip)]
+    url_import: Rc<RefCell<Option<UrlImportDialog>>>,
+    #[serde(skip)]
+    paste_import: Option<PasteImportDialog>,
+    #[serde(skip)]
+    editor: Option<DataFrameEditor>,
+    #[serde(skip)]
+    sample_data: Option<SampleDataDialog>,
+    #[serde(skip)]
+    sql: Option<SqlDialog>,
+    #[cfg(not(target_arch = "wasm32"))]
+    #[serde(skip)]
+    folder_import: Option<FolderImportDialog>,
+    #[cfg(any(feature = "postgres", feature = "mysql"))]
+    #[serde(skip)]
+    database_import: Option<DatabaseImportDialog>,
+    #[cfg(feature = "sqlite")]
+    #[serde(skip)]
+    sqlite_browser: Option<SqliteBrowser>,
+    #[cfg(feature = "cloud")]
+    #[serde(skip)]
+    cloud_import: Option<CloudImportDialog>,
 }
 
 impl Default for App {
@@ -34,6 +100,28 @@ impl Default for App {
             frames: Rc::new(RefCell::new(Vec::new())),
             titles: Rc::new(RefCell::new(Vec::new())),
             df_cols: Rc::new(RefCell::new(HashMap::default())),
+            #[cfg(not(target_arch = "wasm32"))]
+            recent_files: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            lazy_scan: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            imports: Rc::new(RefCell::new(Vec::new())),
+            layouts: HashMap::new(),
+            queries: 0,
+            csv_import: Rc::new(RefCell::new(None)),
+            url_import: Rc::new(RefCell::new(None)),
+            paste_import: None,
+            editor: None,
+            sample_data: None,
+            sql: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            folder_import: None,
+            #[cfg(any(feature = "postgres", feature = "mysql"))]
+            database_import: None,
+            #[cfg(feature = "sqlite")]
+            sqlite_browser: None,
+            #[cfg(feature = "cloud")]
+            cloud_import: None,
         }
     }
 }
@@ -51,11 +139,158 @@ impl App {
         }
         Default::default()
     }
+
+    /// Opens files picked from disk, one container per file. CSV files go through a shared
+    /// import options dialog first, every other format starts reading right away.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_paths(&mut self, paths: Vec<PathBuf>) {
+        let mut csv_files = Vec::new();
+        for path in paths {
+            let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+            if FileFormat::from_path(&path) == FileFormat::Csv {
+                csv_files.push((file_name, ImportSource::Path(path)));
+                continue;
+            }
+            self.load_path(&path, &file_name, &CsvImportOptions::default(), None);
+        }
+        if !csv_files.is_empty() {
+            *self.csv_import.borrow_mut() = Some(CsvImportDialog::new(csv_files));
+        }
+    }
+
+    /// Reopens a recent file with its previous title and CSV options, skipping the dialog.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_recent_file(&mut self, recent: RecentFile) {
+        let options = recent.csv_options.clone().unwrap_or_default();
+        self.load_path(&recent.path, &recent.title, &options, None);
+    }
+
+    /// Starts reading a file from disk on a worker thread. CSV and Parquet files are only
+    /// scanned when lazy scan mode is on. `n_rows` limits CSV files to their first rows.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_path(
+        &self,
+        path: &Path,
+        title: &str,
+        csv_options: &CsvImportOptions,
+        n_rows: Option<usize>,
+    ) {
+        let source = FileSource {
+            path: path.to_path_buf(),
+            csv_options: csv_options.clone(),
+            n_rows,
+        };
+        self.imports
+            .borrow_mut()
+            .push(BackgroundImport::spawn(source, title, self.lazy_scan));
+    }
+
+    /// Turns the finished background imports into containers. CSV files that failed to read
+    /// go back to the import dialog so the options can be adjusted.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn finish_imports(&mut self) {
+        let mut finished = Vec::new();
+        self.imports
+            .borrow_mut()
+            .retain(|import| match import.poll() {
+                Some(result) => {
+                    finished.push((import.title.clone(), import.source.clone(), result));
+                    false
+                }
+                None => true,
+            });
+        for (title, source, result) in finished {
+            let is_csv = FileFormat::from_path(&source.path) == FileFormat::Csv;
+            match result {
+                Ok(data) => {
+                    self.add_recent_file(RecentFile {
+                        path: source.path.clone(),
+                        title: title.clone(),
+                        csv_options: is_csv.then(|| source.csv_options.clone()),
+                    });
+                    let container = DataFrameContainer::from_file(source, &title, data);
+                    add_container(&self.frames, &self.titles, &self.df_cols, container);
+                }
+                Err(e) if is_csv => {
+                    *self.csv_import.borrow_mut() = Some(CsvImportDialog::retry(
+                        &title,
+                        ImportSource::Path(source.path),
+                        source.csv_options,
+                        source.n_rows,
+                        e.to_string(),
+                    ));
+                }
+                Err(e) => log::error!("Could not read {}: {}", source.path.display(), e),
+            }
+        }
+    }
+
+    /// Moves the file to the top of the recent files list.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn add_recent_file(&mut self, recent: RecentFile) {
+        self.recent_files.retain(|r| r.path != recent.path);
+        self.recent_files.insert(0, recent);
+        self.recent_files.truncate(MAX_RECENT_FILES);
+    }
+
+    /// Opens downloaded or uploaded file contents. CSV files go through the import options
+    /// dialog first, every other format is read right away.
+    fn open_bytes(&self, name: &str, bytes: Vec<u8>) -> PolarsResult<()> {
+        let format = FileFormat::readable(Path::new(name))?;
+        if format == FileFormat::Csv {
+            *self.csv_import.borrow_mut() = Some(CsvImportDialog::new(vec![(
+                name.to_string(),
+                ImportSource::Bytes(bytes),
+            )]));
+            return Ok(());
+        }
+        let df = reader::read_bytes(bytes, &format)?;
+        push_container(&self.frames, &self.titles, &self.df_cols, df, name);
+        Ok(())
+    }
+
+    /// Opens the body of a finished URL download, or reports the failure in the dialog.
+    fn finish_download(
+        &self,
+        dialog: &mut UrlImportDialog,
+        response: ehttp::Result<ehttp::Response>,
+    ) {
+        dialog.loading = false;
+        let result = match response {
+            Ok(response) if response.ok => self
+                .open_bytes(&dialog.file_name(), response.bytes)
+                .map_err(|e| e.to_string()),
+            Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(_) => dialog.is_open = false,
+            Err(e) => dialog.error = Some(e),
+        }
+    }
 }
 
 impl eframe::App for App {
     /// Called by the frame work to save state before shutdown.
     fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        // layouts are kept for the open containers and the files that can be reopened from
+        // the recent files, the others would never be restored.
+        let open: Vec<String> = self
+            .frames
+            .borrow()
+            .iter()
+            .flat_map(|map| map.values())
+            .map(|container| container.title.clone())
+            .collect();
+        #[cfg(not(target_arch = "wasm32"))]
+        let recent = &self.recent_files;
+        self.layouts.retain(|title, _| {
+            #[cfg(not(target_arch = "wasm32"))]
+            if recent.iter().any(|r| &r.title == title) {
+                return true;
+            }
+            open.contains(title)
+        });
         eframe::set_value(storage, eframe::APP_KEY, self);
     }
 
@@ -69,62 +304,126 @@ impl eframe::App for App {
                     if ui.button("DataFrame").clicked() {
                         #[cfg(target_arch = "wasm32")]
                         {
-                            let frames = Rc::clone(&self.frames);
-                            let titles = Rc::clone(&self.titles);
-                            let df_cols = Rc::clone(&self.df_cols);
+                            let app = self.clone();
+                            let dialog = reader::FILE_FILTERS
+                                .iter()
+                                .fold(AsyncFileDialog::new(), |dialog, (name, extensions)| {
+                                    dialog.add_filter(*name, extensions)
+                                });
 
                             execute(async move {
-                                let file = AsyncFileDialog::new().pick_file().await;
-
-                                if let Some(file) = file {
-                                    //file.read().await;
-                                    let content = file.read().await;
-                                    let cursor = std::io::Cursor::new(content);
-                                    let df = CsvReader::new(cursor).finish().unwrap();
-                                    let file_name = file.file_name();
-                                    let mut hash = HashMap::new();
-                                    hash.insert(
-                                        file_name.to_string(),
-                                        DataFrameContainer::new(df.clone(), &file_name),
-                                    );
-                                    frames.borrow_mut().push(hash);
-                                    titles.borrow_mut().push(file_name.to_string());
-                                    let cols = df
-                                        .clone()
-                                        .get_column_names()
-                                        .iter()
-                                        .map(|c| c.to_string())
-                                        .collect();
-                                    df_cols.borrow_mut().insert(String::from(file_name), cols);
+                                let files = dialog.pick_files().await;
+
+                                if let Some(files) = files {
+                                    // CSV files share an import options dialog, the other
+                                    // formats are read from the uploaded bytes right away.
+                                    let mut csv_files = Vec::new();
+                                    for file in files {
+                                        let name = file.file_name();
+                                        let content = file.read().await;
+                                        if FileFormat::from_path(Path::new(&name))
+                                            == FileFormat::Csv
+                                        {
+                                            csv_files.push((name, ImportSource::Bytes(content)));
+                                        } else if let Err(e) = app.open_bytes(&name, content) {
+                                            log::error!("Could not read {}: {}", name, e);
+                                        }
+                                    }
+                                    if !csv_files.is_empty() {
+                                        *app.csv_import.borrow_mut() =
+                                            Some(CsvImportDialog::new(csv_files));
+                                    }
                                 }
                             });
                         }
                         #[cfg(not(target_arch = "wasm32"))]
-                        if let Some(path) = FileDialog::new().pick_file() {
-                            let df: DataFrame = CsvReadOptions::default()
-                                .with_has_header(true)
-                                .with_infer_schema_length(Some(10000))
-                                .try_into_reader_with_file_path(Some(path.clone()))
-                                .unwrap()
-                                .finish()
-                                .unwrap();
-                            let file_name: &str = &path.file_name().unwrap().to_str().unwrap();
-                            let mut hash = HashMap::new();
-                            hash.insert(
-                                file_name.to_string(),
-                                DataFrameContainer::new(df.clone(), file_name),
-                            );
-                            self.frames.borrow_mut().push(hash);
-                            let cols = df
-                                .clone()
-                                .get_column_names()
-                                .iter()
-                                .map(|c| c.to_string())
-                                .collect();
-                            self.df_cols
-                                .borrow_mut()
-                                .insert(String::from(file_name), cols);
-                            self.titles.borrow_mut().push(file_name.to_string());
+                        if let Some(paths) = reader::FILE_FILTERS
+                            .iter()
+                            .fold(FileDialog::new(), |dialog, (name, extensions)| {
+                                dialog.add_filter(*name, extensions)
+                            })
+                            .pick_files()
+                        {
+                            self.open_paths(paths);
+                        }
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.button("Folder").clicked() {
+                        if let Some(folder) = FileDialog::new().pick_folder() {
+                            self.folder_import = Some(FolderImportDialog::new(folder));
+                        }
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.menu_button("Recent", |ui| {
+                        if self.recent_files.is_empty() {
+                            ui.label("No recent files");
+                        }
+                        let mut reopen = None;
+                        for recent in &self.recent_files {
+                            if ui
+                                .button(&recent.title)
+                                .on_hover_text(recent.path.display().to_string())
+                                .clicked()
+                            {
+                                reopen = Some(recent.clone());
+                                ui.close_menu();
+                            }
+                        }
+                        if let Some(recent) = reopen {
+                            self.open_recent_file(recent);
+                        }
+                        if !self.recent_files.is_empty() {
+                            ui.separator();
+                            if ui.button("Clear").clicked() {
+                                self.recent_files.clear();
+                            }
+                        }
+                    });
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.checkbox(&mut self.lazy_scan, "Lazy scan CSV/Parquet")
+                        .on_hover_text(
+                            "Only read the first rows; transformations run on the whole file",
+                        );
+                    if ui.button("From URL").clicked() {
+                        *self.url_import.borrow_mut() = Some(UrlImportDialog::default());
+                    }
+                    if ui.button("From Clipboard").clicked() {
+                        self.paste_import = Some(PasteImportDialog::default());
+                    }
+                    if ui.button("Manual Entry").clicked() {
+                        self.editor = Some(DataFrameEditor::default());
+                    }
+                    if ui.button("Sample Data").clicked() {
+                        self.sample_data = Some(SampleDataDialog::default());
+                    }
+                    if ui.button("SQL Query").clicked() {
+                        self.sql = Some(SqlDialog::default());
+                    }
+                    #[cfg(all(
+                        not(target_arch = "wasm32"),
+                        any(feature = "postgres", feature = "mysql")
+                    ))]
+                    if ui.button("From Database").clicked() {
+                        self.database_import = Some(DatabaseImportDialog::default());
+                    }
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "cloud"))]
+                    if ui.button("From Object Storage").clicked() {
+                        self.cloud_import = Some(CloudImportDialog::default());
+                    }
+                    #[cfg(all(not(target_arch = "wasm32"), feature = "sqlite"))]
+                    if ui.button("From SQLite").clicked() {
+                        if let Some(path) = FileDialog::new()
+                            .add_filter("SQLite", &["sqlite", "sqlite3", "db"])
+                            .pick_file()
+                        {
+                            let file_name = path.file_name().unwrap().to_string_lossy();
+                            match database::sqlite_tables(&path) {
+                                Ok(tables) => {
+                                    self.sqlite_browser =
+                                        Some(SqliteBrowser::new(path.clone(), &file_name, tables));
+                                }
+                                Err(e) => log::error!("Could not open {}: {}", path.display(), e),
+                            }
                         }
                     }
                 });
@@ -136,15 +435,331 @@ impl eframe::App for App {
             });
         });
 
+        let mut csv_import = self.csv_import.borrow_mut();
+        if let Some(dialog) = csv_import.as_mut() {
+            dialog.show(ctx);
+            if dialog.import {
+                dialog.import = false;
+                let mut errors = Vec::new();
+                // files that fail to read stay in the dialog so the options can be adjusted.
+                dialog.files.retain(|(title, source)| {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let ImportSource::Path(path) = source {
+                        self.load_path(path, title, &dialog.options, dialog.row_limit);
+                        return false;
+                    }
+                    match reader::read_csv(source, &dialog.options, dialog.row_limit) {
+                        Ok(df) => {
+                            push_container(&self.frames, &self.titles, &self.df_cols, df, title);
+                            false
+                        }
+                        Err(e) => {
+                            errors.push(format!("{}: {}", title, e));
+                            true
+                        }
+                    }
+                });
+                if dialog.files.is_empty() {
+                    dialog.is_open = false;
+                } else {
+                    dialog.error = Some(errors.join("\n"));
+                }
+            }
+            if !dialog.is_open {
+                *csv_import = None;
+            }
+        }
+        drop(csv_import);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(dialog) = self.folder_import.as_mut() {
+            dialog.show(ctx);
+            if std::mem::take(&mut dialog.load) {
+                let folder = dialog.folder.clone();
+                let name = dialog.folder_name();
+                let sources: Vec<reader::FileSource> = dialog
+                    .files
+                    .iter()
+                    .map(|path| reader::FileSource {
+                        path: path.clone(),
+                        csv_options: dialog.csv_options.clone(),
+                        n_rows: None,
+                    })
+                    .collect();
+                let concatenate = dialog.concatenate;
+                let label = format!("{} ({} files)", name, sources.len());
+                let task = BackgroundTask::spawn(move || {
+                    reader::read_folder(&folder, &name, &sources, concatenate)
+                        .map_err(|e| e.to_string())
+                });
+                dialog.running = Some((label, Rc::new(task)));
+            }
+            if let Some(result) = dialog.running.as_ref().and_then(|(_, task)| task.poll()) {
+                dialog.running = None;
+                match result {
+                    Ok(frames) => {
+                        for (title, df) in frames {
+                            push_container(&self.frames, &self.titles, &self.df_cols, df, &title);
+                        }
+                        dialog.is_open = false;
+                    }
+                    Err(e) => dialog.error = Some(e),
+                }
+            }
+            if !dialog.is_open {
+                self.folder_import = None;
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.finish_imports();
+            let imports = self.imports.borrow();
+            let mut titles: Vec<&str> = imports.iter().map(|i| i.title.as_str()).collect();
+            if let Some((label, _)) = self.folder_import.as_ref().and_then(|d| d.running.as_ref()) {
+                titles.push(label);
+            }
+            background::show_progress(&titles, ctx);
+        }
+
+        let mut url_import = self.url_import.borrow_mut();
+        if let Some(dialog) = url_import.as_mut() {
+            dialog.show(ctx);
+            if dialog.load {
+                dialog.load = false;
+                dialog.loading = true;
+                let request = ehttp::Request::get(&dialog.url);
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    dialog.download = Some(BackgroundTask::spawn(move || {
+                        ehttp::fetch_blocking(&request)
+                    }));
+                }
+                #[cfg(target_arch = "wasm32")]
+                {
+                    let app = self.clone();
+                    execute(async move {
+                        let response = ehttp::fetch_async(request).await;
+                        if let Some(dialog) = app.url_import.borrow_mut().as_mut() {
+                            app.finish_download(dialog, response);
+                        }
+                    });
+                }
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(response) = dialog.download.as_ref().and_then(|d| d.poll()) {
+                dialog.download = None;
+                self.finish_download(dialog, response);
+            }
+            if !dialog.is_open {
+                *url_import = None;
+            }
+        }
+        drop(url_import);
+
+        if let Some(dialog) = self.paste_import.as_mut() {
+            dialog.show(ctx);
+            if std::mem::take(&mut dialog.load) {
+                let mut csv_dialog = CsvImportDialog::new(vec![(
+                    format!("clipboard_{}", self.frames.borrow().len()),
+                    ImportSource::Bytes(dialog.text.clone().into_bytes()),
+                )]);
+                csv_dialog.options.separator = dialog.separator().to_string();
+                *self.csv_import.borrow_mut() = Some(csv_dialog);
+                dialog.is_open = false;
+            }
+            if !dialog.is_open {
+                self.paste_import = None;
+            }
+        }
+
+        if let Some(editor) = self.editor.as_mut() {
+            editor.show(ctx);
+            if std::mem::take(&mut editor.create) {
+                match editor.to_dataframe() {
+                    Ok(df) => {
+                        push_container(
+                            &self.frames,
+                            &self.titles,
+                            &self.df_cols,
+                            df,
+                            &editor.title,
+                        );
+                        editor.is_open = false;
+                    }
+                    Err(e) => editor.error = Some(e.to_string()),
+                }
+            }
+            if !editor.is_open {
+                self.editor = None;
+            }
+        }
+
+        if let Some(dialog) = self.sample_data.as_mut() {
+            dialog.show(ctx);
+            if std::mem::take(&mut dialog.create) {
+                match dialog.generate() {
+                    Ok(frames) => {
+                        for (title, df) in frames {
+                            push_container(&self.frames, &self.titles, &self.df_cols, df, &title);
+                        }
+                        dialog.is_open = false;
+                    }
+                    Err(e) => log::error!("Could not generate sample data: {}", e),
+                }
+            }
+            if !dialog.is_open {
+                self.sample_data = None;
+            }
+        }
+
+        if let Some(dialog) = self.sql.as_mut() {
+            // containers made by transformations are not in titles, list them all.
+            dialog.tables = self
+                .frames
+                .borrow()
+                .iter()
+                .flat_map(|map| map.values())
+                .map(|container| container.title.clone())
+                .collect();
+            dialog.show(ctx);
+            if std::mem::take(&mut dialog.run) {
+                let tables = self
+                    .frames
+                    .borrow()
+                    .iter()
+                    .flat_map(|map| map.values())
+                    .map(|container| (container.title.clone(), container.lazy_frame()))
+                    .collect();
+                match sql::run_query(tables, &dialog.query) {
+                    Ok(df) => {
+                        let title = query_title(&mut self.queries);
+                        push_container(&self.frames, &self.titles, &self.df_cols, df, &title);
+                        dialog.error = None;
+                    }
+                    Err(e) => dialog.error = Some(e.to_string()),
+                }
+            }
+            if !dialog.is_open {
+                self.sql = None;
+            }
+        }
+
+        #[cfg(all(
+            not(target_arch = "wasm32"),
+            any(feature = "postgres", feature = "mysql")
+        ))]
+        if let Some(dialog) = self.database_import.as_mut() {
+            dialog.show(ctx);
+            if dialog.run {
+                dialog.run = false;
+                let (connection, sql) = (dialog.connection.clone(), dialog.query.clone());
+                dialog.running = Some(Rc::new(BackgroundTask::spawn(move || {
+                    database::query(&connection, &sql)
+                })));
+            }
+            if let Some(result) = dialog.running.as_ref().and_then(|t| t.poll()) {
+                dialog.running = None;
+                match result {
+                    Ok(df) => {
+                        let title = query_title(&mut self.queries);
+                        push_container(&self.frames, &self.titles, &self.df_cols, df, &title);
+                        dialog.is_open = false;
+                    }
+                    Err(e) => dialog.error = Some(e),
+                }
+            }
+            if !dialog.is_open {
+                self.database_import = None;
+            }
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), feature = "sqlite"))]
+        if let Some(browser) = self.sqlite_browser.as_mut() {
+            browser.show(ctx);
+            let request = if std::mem::take(&mut browser.load_table) {
+                Some((
+                    browser.table_selection.clone(),
+                    database::select_table(&browser.table_selection),
+                ))
+            } else if std::mem::take(&mut browser.run_query) {
+                let title = query_title(&mut self.queries);
+                Some((title, browser.query.clone()))
+            } else {
+                None
+            };
+            if let Some((title, sql)) = request {
+                let path = browser.path.clone();
+                let task = BackgroundTask::spawn(move || database::query_sqlite(&path, &sql));
+                browser.running = Some((title, Rc::new(task)));
+            }
+            let finished = browser
+                .running
+                .as_ref()
+                .and_then(|(title, task)| Some((title.clone(), task.poll()?)));
+            if let Some((title, result)) = finished {
+                browser.running = None;
+                match result {
+                    Ok(df) => push_container(&self.frames, &self.titles, &self.df_cols, df, &title),
+                    Err(e) => browser.error = Some(e),
+                }
+            }
+            if !browser.is_open {
+                self.sqlite_browser = None;
+            }
+        }
+
+        #[cfg(all(not(target_arch = "wasm32"), feature = "cloud"))]
+        if let Some(dialog) = self.cloud_import.as_mut() {
+            dialog.show(ctx);
+            if std::mem::take(&mut dialog.load) {
+                let (path, credentials) = (dialog.path.clone(), dialog.credentials.clone());
+                dialog.running = Some(Rc::new(BackgroundTask::spawn(move || {
+                    reader::read_cloud(&path, &credentials).map_err(|e| e.to_string())
+                })));
+            }
+            if let Some(result) = dialog.running.as_ref().and_then(|t| t.poll()) {
+                dialog.running = None;
+                match result {
+                    Ok(df) => {
+                        let title = dialog.file_name();
+                        push_container(&self.frames, &self.titles, &self.df_cols, df, &title);
+                        dialog.is_open = false;
+                    }
+                    Err(e) => dialog.error = Some(e),
+                }
+            }
+            if !dialog.is_open {
+                self.cloud_import = None;
+            }
+        }
+
         egui::CentralPanel::default().show(ctx, |_ui| {
             let mut temp_frames = Vec::new(); // Temporary vector to hold the filtered frames
             let temp_joins = &self.frames.borrow_mut().clone();
             let nr_frames = &self.frames.borrow_mut().len();
 
             for map in self.frames.borrow_mut().iter_mut() {
-                for (_key, val) in map {
+                for val in map.values_mut() {
                     let frame_refcell = val;
+                    // The data view arrangement is kept by title, across restarts.
+                    if !frame_refcell.view.restored {
+                        if let Some(layout) = self.layouts.get(&frame_refcell.title) {
+                            frame_refcell.view.set_layout(layout, &frame_refcell.data);
+                        }
+                        frame_refcell.view.restored = true;
+                    }
                     frame_refcell.show(ctx);
+                    // Columns change with in place transformations, e.g. a rename.
+                    if let Some(cols) = self.df_cols.borrow_mut().get_mut(&frame_refcell.title) {
+                        if *cols != frame_refcell.columns {
+                            cols.clone_from(&frame_refcell.columns);
+                        }
+                    }
+                    let layout = frame_refcell.view.layout();
+                    if self.layouts.get(&frame_refcell.title) != Some(&layout) {
+                        self.layouts.insert(frame_refcell.title.clone(), layout);
+                    }
 
                     // Filter creates a new DataFrameContainer. InPlace option updates the
                     // existing container with the new one. The New option displays the filtered
@@ -173,12 +788,34 @@ impl eframe::App for App {
                                 frame_refcell.filter.filtered_data = None;
                             }
                             true => {
-                                frame_refcell.data = filtered_df.data.clone();
-                                frame_refcell.shape = filtered_df.data.shape().clone();
+                                frame_refcell.set_data(filtered_df.data);
+                                frame_refcell.filter.filtered_data = None;
+                            }
+                        }
+                    }
+
+                    // Concat looks up the other container like a join does.
+                    frame_refcell.concat.df_list = self.titles.borrow().clone();
+                    if std::mem::take(&mut frame_refcell.concat.concat) {
+                        match get_container(temp_joins, &frame_refcell.concat.df_selection) {
+                            Some(other) => frame_refcell.concat_dataframe(&other),
+                            None => {
+                                frame_refcell.concat.error = Some(format!(
+                                    "'{}' is no longer open",
+                                    frame_refcell.concat.df_selection
+                                ))
                             }
                         }
                     }
 
+                    // Results of transformations set to "New" open in new windows.
+                    for (name, df) in frame_refcell.new_frames.drain(..) {
+                        let new_title = format!("{}_{}{}", name, &frame_refcell.title, &nr_frames);
+                        let mut new_hash = HashMap::new();
+                        new_hash.insert(new_title.clone(), DataFrameContainer::new(df, &new_title));
+                        temp_frames.push(new_hash);
+                    }
+
                     // Join requires the selection of another DataFrameContainer in the frames list
                     // and the mapped columns stored in df_cols.
                     frame_refcell.join.df_list = self.titles.borrow_mut().clone();
@@ -205,6 +842,43 @@ impl eframe::App for App {
     }
 }
 
+/// Title of the next query result, e.g. `query_3`. Numbered by a counter kept across restarts
+/// rather than the number of frames, which starts over with each session, so a new result
+/// doesn't take the title, and the saved layout, of an earlier one.
+fn query_title(queries: &mut usize) -> String {
+    *queries += 1;
+    format!("query_{}", queries)
+}
+
+/// Wraps a loaded DataFrame in a new container and registers its title and columns
+/// so it can be selected by other containers (e.g. as the right side of a join).
+fn push_container(
+    frames: &RefCell<Vec<HashMap<String, DataFrameContainer>>>,
+    titles: &RefCell<Vec<String>>,
+    df_cols: &RefCell<HashMap<String, Vec<String>>>,
+    df: DataFrame,
+    title: &str,
+) {
+    add_container(frames, titles, df_cols, DataFrameContainer::new(df, title));
+}
+
+/// Registers an already built container, e.g. one in lazy mode.
+fn add_container(
+    frames: &RefCell<Vec<HashMap<String, DataFrameContainer>>>,
+    titles: &RefCell<Vec<String>>,
+    df_cols: &RefCell<HashMap<String, Vec<String>>>,
+    container: DataFrameContainer,
+) {
+    let title = container.title.clone();
+    df_cols
+        .borrow_mut()
+        .insert(title.clone(), container.columns.clone());
+    titles.borrow_mut().push(title.clone());
+    let mut hash = HashMap::new();
+    hash.insert(title, container);
+    frames.borrow_mut().push(hash);
+}
+
 #[cfg(target_arch = "wasm32")]
 fn execute<F: Future<Output = ()> + 'static>(f: F) {
     wasm_bindgen_futures::spawn_local(f);
@@ -0,0 +1,84 @@
+use crate::reader::{FileSource, LoadedData};
+use egui::Window;
+use polars::prelude::*;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+
+/// A file being read on a worker thread, so large files don't freeze the UI.
+#[derive(Debug)]
+pub struct BackgroundImport {
+    pub title: String,
+    pub source: FileSource,
+    receiver: Receiver<PolarsResult<LoadedData>>,
+}
+
+impl BackgroundImport {
+    pub fn spawn(source: FileSource, title: &str, lazy: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let worker_source = source.clone();
+        std::thread::spawn(move || {
+            // the receiver is gone if the app closed in the meantime.
+            let _ = sender.send(worker_source.load(lazy));
+        });
+        Self {
+            title: title.to_string(),
+            source,
+            receiver,
+        }
+    }
+
+    /// The read result once the worker is done, `None` while it's still reading.
+    pub fn poll(&self) -> Option<PolarsResult<LoadedData>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err(polars_err!(ComputeError: "the import was interrupted")))
+            }
+        }
+    }
+}
+
+/// Work other than a file import running on a worker thread, e.g. a download, so that it
+/// doesn't freeze the UI while it waits.
+#[derive(Debug)]
+pub struct BackgroundTask<T> {
+    receiver: Receiver<Result<T, String>>,
+}
+
+impl<T: Send + 'static> BackgroundTask<T> {
+    pub fn spawn(work: impl FnOnce() -> Result<T, String> + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            // the receiver is gone if the dialog was closed in the meantime.
+            let _ = sender.send(work());
+        });
+        Self { receiver }
+    }
+
+    /// The result once the worker is done, `None` while it's still running.
+    pub fn poll(&self) -> Option<Result<T, String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(String::from("the task was interrupted"))),
+        }
+    }
+}
+
+/// Spinner window listing the files still being read.
+pub fn show_progress(titles: &[&str], ctx: &egui::Context) {
+    if titles.is_empty() {
+        return;
+    }
+    Window::new("Loading")
+        .collapsible(false)
+        .resizable(false)
+        .show(ctx, |ui| {
+            for title in titles {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(*title);
+                });
+            }
+        });
+}
@@ -0,0 +1,305 @@
+use crate::utils::interpolate_linear;
+use polars::prelude::*;
+
+/// Drops the rows with a null in any column, or in any of a subset of columns.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameDropNulls {
+    /// Only look for nulls in the subset instead of all the columns.
+    pub use_subset: bool,
+    pub subset: Vec<String>,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameDropNulls {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        let subset = match self.use_subset {
+            true if self.subset.is_empty() => {
+                polars_bail!(ComputeError: "select the columns to look for nulls in")
+            }
+            true => Some(self.subset.iter().map(|c| col(c)).collect()),
+            false => None,
+        };
+        df.drop_nulls(subset).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillStrategy {
+    Value,
+    Forward,
+    Backward,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Zero,
+    Linear,
+    Nearest,
+}
+
+impl FillStrategy {
+    pub const ALL: [FillStrategy; 10] = [
+        FillStrategy::Value,
+        FillStrategy::Forward,
+        FillStrategy::Backward,
+        FillStrategy::Mean,
+        FillStrategy::Median,
+        FillStrategy::Min,
+        FillStrategy::Max,
+        FillStrategy::Zero,
+        FillStrategy::Linear,
+        FillStrategy::Nearest,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FillStrategy::Value => "Value",
+            FillStrategy::Forward => "Forward fill",
+            FillStrategy::Backward => "Backward fill",
+            FillStrategy::Mean => "Mean",
+            FillStrategy::Median => "Median",
+            FillStrategy::Min => "Min",
+            FillStrategy::Max => "Max",
+            FillStrategy::Zero => "Zero",
+            FillStrategy::Linear => "Linear interpolation",
+            FillStrategy::Nearest => "Nearest value",
+        }
+    }
+
+    /// Whether the strategy only makes sense for numbers.
+    fn numeric(&self) -> bool {
+        matches!(
+            self,
+            FillStrategy::Mean
+                | FillStrategy::Median
+                | FillStrategy::Zero
+                | FillStrategy::Linear
+                | FillStrategy::Nearest
+        )
+    }
+}
+
+/// Replaces the nulls of a column, or of all columns, following a strategy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameFillNulls {
+    /// Column to fill, all columns when empty.
+    pub column: String,
+    pub strategy: FillStrategy,
+    /// Value of the Value strategy, cast to the type of each column.
+    pub value: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameFillNulls {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            strategy: FillStrategy::Value,
+            value: String::new(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameFillNulls {
+    /// Fills the nulls of the column. With all columns, the numeric strategies leave the
+    /// other columns alone.
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        let schema = df.schema()?;
+        let columns: Vec<(&SmartString, &DataType)> = match self.column.is_empty() {
+            true => schema
+                .iter()
+                .filter(|(_, dtype)| !self.strategy.numeric() || dtype.is_numeric())
+                .collect(),
+            false => match schema.get(&self.column) {
+                Some(dtype) if self.strategy.numeric() && !dtype.is_numeric() => {
+                    polars_bail!(ComputeError: "'{}' is {}, {} needs numbers", self.column, dtype, self.strategy.label())
+                }
+                Some(_) => schema.iter().filter(|(c, _)| **c == self.column).collect(),
+                None => polars_bail!(ColumnNotFound: "{}", self.column),
+            },
+        };
+        let exprs: Vec<Expr> = columns
+            .into_iter()
+            .map(|(name, dtype)| {
+                let c = col(name);
+                match self.strategy {
+                    FillStrategy::Value => {
+                        c.fill_null(lit(self.value.clone()).strict_cast(dtype.clone()))
+                    }
+                    FillStrategy::Forward => c.forward_fill(None),
+                    FillStrategy::Backward => c.backward_fill(None),
+                    FillStrategy::Mean => c.clone().fill_null(c.mean()),
+                    FillStrategy::Median => c.clone().fill_null(c.median()),
+                    FillStrategy::Min => c.clone().fill_null(c.min()),
+                    FillStrategy::Max => c.clone().fill_null(c.max()),
+                    FillStrategy::Zero => c.fill_null(lit(0).strict_cast(dtype.clone())),
+                    // interpolating leaves the nulls before the first and after the last value.
+                    FillStrategy::Linear => interpolate_linear(c, dtype),
+                    FillStrategy::Nearest => c.interpolate(InterpolationMethod::Nearest),
+                }
+            })
+            .collect();
+        df.with_columns(exprs).collect()
+    }
+}
+
+/// Drops duplicate rows, comparing all columns or a subset of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameDistinct {
+    pub use_subset: bool,
+    pub subset: Vec<String>,
+    /// Which row of each set of duplicates stays.
+    pub keep: UniqueKeepStrategy,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameDistinct {
+    fn default() -> Self {
+        Self {
+            use_subset: false,
+            subset: Vec::new(),
+            keep: UniqueKeepStrategy::First,
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameDistinct {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        let subset = match self.use_subset {
+            true if self.subset.is_empty() => {
+                polars_bail!(ComputeError: "select the columns to compare")
+            }
+            true => Some(self.subset.clone()),
+            false => None,
+        };
+        df.unique_stable(subset, self.keep).collect()
+    }
+}
+
+/// Replaces some values of a column: exact values, e.g. "N/A", "na" and "-", or the
+/// matches of a regex in a text column.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameReplace {
+    pub column: String,
+    /// Comma separated values, or the regex.
+    pub find: String,
+    pub regex: bool,
+    pub value: String,
+    /// Replace with null instead of the value.
+    pub null: bool,
+    pub inplace: bool,
+    pub error: Option<String>,
+    /// Number of cells the replacement changes, for the inputs it was counted for.
+    pub preview: Option<(String, String, bool, Result<usize, String>)>,
+}
+
+impl DataFrameReplace {
+    fn values(&self) -> Vec<&str> {
+        self.find.split(',').map(|v| v.trim()).collect()
+    }
+
+    /// Whether a cell is changed by the replacement. Exact values are compared with the
+    /// cells as text, so numbers can be replaced too.
+    fn matches(&self) -> Expr {
+        match self.regex {
+            true => col(&self.column)
+                .str()
+                .contains(lit(self.find.clone()), true),
+            false => col(&self.column)
+                .cast(DataType::String)
+                .is_in(lit(Series::new("", self.values()))),
+        }
+    }
+
+    /// Counts the cells of the loaded rows that would change, once for each change of the
+    /// inputs. A lazy frame isn't scanned for the count.
+    pub fn update_preview(&mut self, df: &DataFrame) {
+        let key = (self.column.clone(), self.find.clone(), self.regex);
+        if self.column.is_empty() || self.find.is_empty() {
+            self.preview = None;
+            return;
+        }
+        if let Some((column, find, regex, _)) = &self.preview {
+            if (column, find, regex) == (&key.0, &key.1, &key.2) {
+                return;
+            }
+        }
+        let count = df
+            .clone()
+            .lazy()
+            .select([self.matches().sum()])
+            .collect()
+            .and_then(|df| Ok(df.get_columns()[0].cast(&IDX_DTYPE)?.idx()?.get(0)))
+            .map(|count| count.unwrap_or(0) as usize)
+            .map_err(|e| e.to_string());
+        self.preview = Some((key.0, key.1, key.2, count));
+    }
+
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        let dtype = df.schema()?.try_get(&self.column)?.clone();
+        let value = match self.null {
+            true => lit(NULL).cast(dtype.clone()),
+            false => lit(self.value.clone()).strict_cast(dtype.clone()),
+        };
+        let replaced = match (self.regex, self.null) {
+            (true, false) => col(&self.column).str().replace_all(
+                lit(self.find.clone()),
+                lit(self.value.clone()),
+                false,
+            ),
+            _ => when(self.matches())
+                .then(value)
+                .otherwise(col(&self.column)),
+        };
+        df.with_column(replaced.alias(&self.column)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(strategy: FillStrategy) -> DataFrame {
+        let df = df! {
+            "i" => [Some(1i32), None, None, Some(2), None],
+            "f" => [Some(1.0f64), None, Some(2.0), None, None],
+        }
+        .unwrap();
+        let fill = DataFrameFillNulls {
+            strategy,
+            ..Default::default()
+        };
+        fill.apply(df.lazy()).unwrap()
+    }
+
+    #[test]
+    fn linear_interpolation_keeps_integers() {
+        let df = fill(FillStrategy::Linear);
+        assert_eq!(
+            df.column("i").unwrap(),
+            &Series::new("i", [Some(1i32), Some(1), Some(2), Some(2), None])
+        );
+        assert_eq!(
+            df.column("f").unwrap(),
+            &Series::new("f", [Some(1.0f64), Some(1.5), Some(2.0), None, None])
+        );
+    }
+
+    #[test]
+    fn numeric_strategy_rejects_text() {
+        let df = df! { "s" => [Some("a"), None] }.unwrap();
+        let fill = DataFrameFillNulls {
+            column: String::from("s"),
+            strategy: FillStrategy::Linear,
+            ..Default::default()
+        };
+        assert!(fill.apply(df.lazy()).is_err());
+    }
+}
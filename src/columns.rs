@@ -0,0 +1,193 @@
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// Keeps or drops a set of columns.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameColumns {
+    pub selected: Vec<String>,
+    /// Keep the selected columns, otherwise drop them.
+    pub keep: bool,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameColumns {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.keep && self.selected.is_empty() {
+            polars_bail!(ComputeError: "select at least one column to keep");
+        }
+        match self.keep {
+            true => df.select(self.selected.iter().map(|c| col(c)).collect::<Vec<_>>()),
+            false => df.drop(self.selected.iter().map(|c| c.as_str())),
+        }
+        .collect()
+    }
+}
+
+/// New names for the columns, edited one by one or in bulk.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameRename {
+    /// New name of each column, in the column order.
+    pub names: Vec<String>,
+    pub prefix: String,
+    pub suffix: String,
+    pub find: String,
+    pub replace: String,
+    pub error: Option<String>,
+}
+
+impl DataFrameRename {
+    /// Adds the prefix and suffix to all the names.
+    pub fn add_affixes(&mut self) {
+        for name in &mut self.names {
+            *name = format!("{}{}{}", self.prefix, name, self.suffix);
+        }
+    }
+
+    /// Replaces a text in all the names.
+    pub fn find_replace(&mut self) {
+        if self.find.is_empty() {
+            return;
+        }
+        for name in &mut self.names {
+            *name = name.replace(&self.find, &self.replace);
+        }
+    }
+
+    /// The columns whose name changed, with their new name.
+    pub fn renames(&self, columns: &[String]) -> PolarsResult<Vec<(String, String)>> {
+        for (i, name) in self.names.iter().enumerate() {
+            if name.is_empty() {
+                polars_bail!(ComputeError: "column '{}' needs a name", columns[i]);
+            }
+            if self.names[..i].contains(name) {
+                polars_bail!(Duplicate: "column name '{}' is used twice", name);
+            }
+        }
+        Ok(columns
+            .iter()
+            .zip(&self.names)
+            .filter(|(column, name)| column != name)
+            .map(|(column, name)| (column.clone(), name.clone()))
+            .collect())
+    }
+}
+
+/// A new order for the columns.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameReorder {
+    pub order: Vec<String>,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameReorder {
+    /// Moves the column at `i` up (towards the first column) or down.
+    pub fn move_column(&mut self, i: usize, up: bool) {
+        match up {
+            true if i > 0 => self.order.swap(i, i - 1),
+            false if i + 1 < self.order.len() => self.order.swap(i, i + 1),
+            _ => {}
+        }
+    }
+
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        df.select(self.order.iter().map(|c| col(c)).collect::<Vec<_>>())
+            .collect()
+    }
+}
+
+/// A first column numbering the rows from `offset`, as a key for data without one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameRowIndex {
+    pub name: String,
+    pub offset: u32,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameRowIndex {
+    fn default() -> Self {
+        Self {
+            name: String::from("index"),
+            offset: 0,
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameRowIndex {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            polars_bail!(ComputeError: "the index column needs a name");
+        }
+        df.with_row_index(name, Some(self.offset)).collect()
+    }
+}
+
+/// Types offered for casting columns.
+pub const CAST_TYPES: [DataType; 10] = [
+    DataType::Boolean,
+    DataType::Int32,
+    DataType::Int64,
+    DataType::UInt32,
+    DataType::UInt64,
+    DataType::Float32,
+    DataType::Float64,
+    DataType::String,
+    DataType::Date,
+    DataType::Datetime(TimeUnit::Microseconds, None),
+];
+
+/// New types picked for the columns in the Data Types window.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameCast {
+    pub targets: HashMap<String, DataType>,
+    /// Why the cast of a column failed.
+    pub errors: HashMap<String, String>,
+}
+
+impl DataFrameCast {
+    /// Casts the columns to their new type. A column that can't be cast keeps its type and
+    /// its target, with the error, while the other columns are cast.
+    pub fn apply(&mut self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        self.errors.clear();
+        // collected once, each column is then cast in memory.
+        let mut result = df.collect()?;
+        for (column, dtype) in &self.targets {
+            match result.column(column)?.strict_cast(dtype) {
+                Ok(cast) => {
+                    result.with_column(cast)?;
+                }
+                Err(e) => {
+                    self.errors.insert(column.clone(), e.to_string());
+                }
+            }
+        }
+        self.targets.retain(|c, _| self.errors.contains_key(c));
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_casts_keep_their_target() {
+        let df = df! {
+            "n" => ["1", "2"],
+            "s" => ["1", "x"],
+        }
+        .unwrap();
+        let mut cast = DataFrameCast::default();
+        cast.targets.insert(String::from("n"), DataType::Int64);
+        cast.targets.insert(String::from("s"), DataType::Int64);
+        let result = cast.apply(df.lazy()).unwrap();
+        assert_eq!(result.dtypes(), [DataType::Int64, DataType::String]);
+        assert!(cast.errors.contains_key("s"));
+        assert_eq!(cast.targets.keys().collect::<Vec<_>>(), ["s"]);
+    }
+}
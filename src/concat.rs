@@ -0,0 +1,100 @@
+use polars::prelude::*;
+
+/// Appends the rows, or the columns, of another container, picked like the right side of a
+/// join.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameConcat {
+    pub df_selection: String,
+    pub df_list: Vec<String>,
+    /// Glue the columns side by side instead of appending the rows.
+    pub horizontal: bool,
+    /// Added to the names of the other columns already in this container.
+    pub suffix: String,
+    pub inplace: bool,
+    /// Set by the Concat button, the app looks up the other container.
+    pub concat: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameConcat {
+    fn default() -> Self {
+        Self {
+            df_selection: String::default(),
+            df_list: Vec::new(),
+            horizontal: false,
+            suffix: String::from("_right"),
+            inplace: false,
+            concat: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameConcat {
+    pub fn apply(&self, df: LazyFrame, other: LazyFrame) -> PolarsResult<DataFrame> {
+        match self.horizontal {
+            true => self.hstack(df, other),
+            false => self.vstack(df, other),
+        }
+    }
+
+    /// The columns of `df` followed by the columns of `other`, which needs as many rows.
+    fn hstack(&self, df: LazyFrame, other: LazyFrame) -> PolarsResult<DataFrame> {
+        let df = df.collect()?;
+        let other = other.collect()?;
+        if df.height() != other.height() {
+            polars_bail!(
+                ShapeMismatch: "can't add the columns of '{}': it has {} rows, not {}",
+                self.df_selection, other.height(), df.height()
+            );
+        }
+        if self.suffix.is_empty() {
+            polars_bail!(ComputeError: "the suffix for duplicate column names can't be empty");
+        }
+        let mut names: Vec<String> = df
+            .get_column_names()
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        let mut columns = Vec::new();
+        for series in other.get_columns() {
+            let mut name = series.name().to_string();
+            while names.contains(&name) {
+                name.push_str(&self.suffix);
+            }
+            names.push(name.clone());
+            columns.push(series.clone().with_name(&name));
+        }
+        df.hstack(&columns)
+    }
+
+    /// The rows of `df` followed by the rows of `other`, which needs the same columns with the
+    /// same types, in any order.
+    fn vstack(&self, mut df: LazyFrame, mut other: LazyFrame) -> PolarsResult<DataFrame> {
+        let schema = df.schema()?;
+        let other_schema = other.schema()?;
+        let mut problems = Vec::new();
+        for (name, dtype) in schema.iter() {
+            match other_schema.get(name) {
+                None => problems.push(format!("'{}' is missing", name)),
+                Some(other_dtype) if other_dtype != dtype => problems.push(format!(
+                    "'{}' is {} here and {} there",
+                    name, dtype, other_dtype
+                )),
+                Some(_) => {}
+            }
+        }
+        for name in other_schema.iter_names() {
+            if !schema.contains(name) {
+                problems.push(format!("'{}' is only there", name));
+            }
+        }
+        if !problems.is_empty() {
+            polars_bail!(
+                SchemaMismatch: "can't append '{}': {}", self.df_selection, problems.join(", ")
+            );
+        }
+        let other = other.select(schema.iter_names().map(|c| col(c)).collect::<Vec<_>>());
+        concat([df, other], UnionArgs::default())?.collect()
+    }
+}
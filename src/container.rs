@@ -1,34 +1,112 @@
 use crate::aggregate::*;
+use crate::anonymize::{AnonymizeMode, DataFrameAnonymize};
+use crate::clean::{
+    DataFrameDistinct, DataFrameDropNulls, DataFrameFillNulls, DataFrameReplace, FillStrategy,
+};
+use crate::columns::{
+    DataFrameCast, DataFrameColumns, DataFrameRename, DataFrameReorder, DataFrameRowIndex,
+    CAST_TYPES,
+};
+use crate::concat::DataFrameConcat;
+use crate::dates::{
+    DataFrameDateParts, DataFrameParseDates, DataFrameTimeZone, DataFrameUpsample, DatePart,
+};
+use crate::encode::{DataFrameBin, DataFrameLabelEncode, DataFrameOneHot};
+use crate::export::{self, DataFrameExport, ExportCompression, ExportFormat};
+use crate::expression::Operand;
 use crate::filter::*;
-use crate::join::DataFrameJoin;
+use crate::formula::DataFrameFormula;
+use crate::join::{DataFrameJoin, CROSS_JOIN_CONFIRM_ROWS};
 use crate::melt::DataFrameMelt;
-use crate::utils::{display_dataframe, get_container};
-use egui::{ComboBox, Grid, TextEdit, Window};
-use polars::prelude::DataFrameJoinOps;
+use crate::pivot::{DataFramePivot, DataFrameTranspose, PivotFunc};
+use crate::reader::LazySource;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::reader::{FileSource, LoadedData};
+use crate::sample::{sample_rows, DataFrameSample};
+use crate::slice::{DataFrameSlice, SliceMode};
+use crate::sort::{DataFrameSort, SortKey};
+use crate::strings::{
+    DataFrameConcatColumns, DataFrameExtract, DataFrameSplit, DataFrameStringOp, StringOp,
+};
+use crate::table::{parse_value, TableView};
+use crate::utils::{column_checkboxes, display_dataframe, get_container};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::watcher::FileWatcher;
+use crate::window::{
+    ChangeOp, DataFrameChange, DataFrameRank, DataFrameRolling, DataFrameWindow, RollingFunc,
+    WindowFunc, RANK_METHODS,
+};
+use egui::{ComboBox, DragValue, Grid, TextEdit, Window};
 use polars::prelude::*;
 use std::collections::HashMap;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub struct DataFrameContainer {
     pub title: String,
     pub shape: (usize, usize),
     pub data: DataFrame,
+    pub lazy: Option<LazySource>,
     pub columns: Vec<String>,
     pub data_display: bool,
     pub is_open: bool,
     pub show_datatypes: bool,
+    pub cast: DataFrameCast,
     pub filter: DataFrameFilter,
     pub aggregate: DataFrameAggregate,
     pub melt: DataFrameMelt,
+    pub pivot: DataFramePivot,
+    pub transpose: DataFrameTranspose,
     pub join: DataFrameJoin,
+    pub concat: DataFrameConcat,
+    pub export: DataFrameExport,
+    pub view: TableView,
+    /// Number of rows of the head, tail and sample views.
+    pub peek_rows: usize,
+    /// Head, tail or sample view being shown, with its label.
+    pub peek: Option<(String, DataFrame)>,
+    pub sort: DataFrameSort,
+    pub sample: DataFrameSample,
+    pub slice: DataFrameSlice,
+    pub select_columns: DataFrameColumns,
+    pub rename: DataFrameRename,
+    pub reorder: DataFrameReorder,
+    pub row_index: DataFrameRowIndex,
+    pub formula: DataFrameFormula,
+    pub window: DataFrameWindow,
+    pub rolling: DataFrameRolling,
+    pub change: DataFrameChange,
+    pub rank: DataFrameRank,
+    pub bin: DataFrameBin,
+    pub one_hot: DataFrameOneHot,
+    pub label_encode: DataFrameLabelEncode,
+    pub parse_dates: DataFrameParseDates,
+    pub date_parts: DataFrameDateParts,
+    pub time_zone: DataFrameTimeZone,
+    pub upsample: DataFrameUpsample,
+    pub drop_nulls: DataFrameDropNulls,
+    pub fill_nulls: DataFrameFillNulls,
+    pub distinct: DataFrameDistinct,
+    pub replace: DataFrameReplace,
+    pub string_op: DataFrameStringOp,
+    pub extract: DataFrameExtract,
+    pub concat_columns: DataFrameConcatColumns,
+    pub split: DataFrameSplit,
+    pub anonymize: DataFrameAnonymize,
+    /// Results to open in new containers, with the name of the transformation that made them.
+    pub new_frames: Vec<(String, DataFrame)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub source: Option<FileSource>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub watcher: Option<FileWatcher>,
 }
 
 impl DataFrameContainer {
     pub fn new(df: DataFrame, title: &str) -> Self {
         Self {
-            title: String::from(format!("{}", String::from(title))),
+            title: String::from(title),
             shape: df.shape(),
             data: df.clone(),
+            lazy: None,
             columns: df
                 .get_column_names()
                 .iter()
@@ -37,92 +115,292 @@ impl DataFrameContainer {
             data_display: false,
             is_open: true,
             show_datatypes: false,
+            cast: DataFrameCast::default(),
             filter: DataFrameFilter::default(),
             aggregate: DataFrameAggregate::default(),
             melt: DataFrameMelt::default(),
+            pivot: DataFramePivot::default(),
+            transpose: DataFrameTranspose::default(),
             join: DataFrameJoin::default(),
+            concat: DataFrameConcat::default(),
+            export: DataFrameExport::default(),
+            view: TableView::default(),
+            peek_rows: 10,
+            peek: None,
+            sort: DataFrameSort::default(),
+            sample: DataFrameSample::default(),
+            slice: DataFrameSlice::default(),
+            select_columns: DataFrameColumns {
+                keep: true,
+                ..Default::default()
+            },
+            rename: DataFrameRename::default(),
+            reorder: DataFrameReorder::default(),
+            row_index: DataFrameRowIndex::default(),
+            formula: DataFrameFormula::default(),
+            window: DataFrameWindow::default(),
+            rolling: DataFrameRolling::default(),
+            change: DataFrameChange::default(),
+            rank: DataFrameRank::default(),
+            bin: DataFrameBin::default(),
+            one_hot: DataFrameOneHot::default(),
+            label_encode: DataFrameLabelEncode::default(),
+            parse_dates: DataFrameParseDates::default(),
+            date_parts: DataFrameDateParts::default(),
+            time_zone: DataFrameTimeZone::default(),
+            upsample: DataFrameUpsample::default(),
+            drop_nulls: DataFrameDropNulls::default(),
+            fill_nulls: DataFrameFillNulls::default(),
+            distinct: DataFrameDistinct::default(),
+            replace: DataFrameReplace::default(),
+            string_op: DataFrameStringOp::default(),
+            extract: DataFrameExtract::default(),
+            concat_columns: DataFrameConcatColumns::default(),
+            split: DataFrameSplit::default(),
+            anonymize: DataFrameAnonymize::default(),
+            new_frames: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            source: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            watcher: None,
         }
     }
 
+    /// Creates a container from a file read from disk, remembering it as the container source.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file(source: FileSource, title: &str, data: LoadedData) -> Self {
+        let mut container = Self::new(DataFrame::empty(), title);
+        container.set_loaded(data);
+        container.source = Some(source);
+        container
+    }
+
+    /// Replaces the data with a file read from disk. Lazily scanned files only keep their
+    /// first rows in `data`.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn set_loaded(&mut self, data: LoadedData) {
+        match data {
+            LoadedData::Eager(df) => self.set_data(df),
+            LoadedData::Lazy {
+                lf,
+                preview,
+                height,
+            } => {
+                self.set_data(preview);
+                self.shape.0 = height;
+                self.lazy = Some(LazySource(*lf));
+            }
+        }
+    }
+
+    /// Re-reads the source file after it changed on disk, keeping lazy mode if it was on.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn reload(&mut self) -> PolarsResult<()> {
+        if let Some(source) = &self.source {
+            let data = source.load(self.lazy.is_some())?;
+            self.set_loaded(data);
+        }
+        if let Some(watcher) = &self.watcher {
+            watcher.reset();
+        }
+        Ok(())
+    }
+
+    /// The full data as a LazyFrame, whether the container is in lazy mode or not.
+    pub fn lazy_frame(&self) -> LazyFrame {
+        match &self.lazy {
+            Some(source) => source.0.clone(),
+            None => self.data.clone().lazy(),
+        }
+    }
+
+    /// Replaces the data with a transformation result, leaving lazy mode.
+    pub fn set_data(&mut self, df: DataFrame) {
+        self.shape = df.shape();
+        self.columns = df
+            .get_column_names()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        self.rename.names = self.columns.clone();
+        self.reorder.order = self.columns.clone();
+        self.replace.preview = None;
+        self.extract.preview = None;
+        self.parse_dates.preview = None;
+        self.data = df;
+        self.lazy = None;
+        self.view.invalidate();
+    }
+
+    /// Renames columns, staying in lazy mode, and follows the renames in the columns picked
+    /// for the transformations.
+    fn rename_columns(&mut self, renames: &[(String, String)]) -> PolarsResult<()> {
+        let names: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| match renames.iter().find(|(old, _)| old == c) {
+                Some((_, new)) => new.clone(),
+                None => c.clone(),
+            })
+            .collect();
+        let mut df = self.data.clone();
+        df.set_column_names(&names)?;
+        let lazy = self.lazy.take().map(|source| {
+            let exprs: Vec<Expr> = self
+                .columns
+                .iter()
+                .zip(&names)
+                .map(|(c, name)| col(c).alias(name))
+                .collect();
+            LazySource(source.0.select(exprs))
+        });
+        let height = self.shape.0;
+        self.set_data(df);
+        if lazy.is_some() {
+            self.shape.0 = height;
+            self.lazy = lazy;
+        }
+
+        let rename = |c: &mut String| {
+            if let Some((_, new)) = renames.iter().find(|(old, _)| old == c) {
+                *c = new.clone();
+            }
+        };
+        self.filter
+            .root
+            .for_each_condition(&mut |c| rename(&mut c.column));
+        rename(&mut self.aggregate.grp_selection);
+        rename(&mut self.aggregate.agg_selection);
+        self.aggregate.groupby.iter_mut().for_each(rename);
+        self.aggregate.aggcols.iter_mut().for_each(rename);
+        rename(&mut self.melt.id_selection);
+        rename(&mut self.melt.val_selection);
+        self.melt.id_vars.iter_mut().for_each(rename);
+        self.melt.value_vars.iter_mut().for_each(rename);
+        self.pivot.index.iter_mut().for_each(rename);
+        rename(&mut self.pivot.on);
+        rename(&mut self.pivot.values);
+        rename(&mut self.transpose.header);
+        rename(&mut self.join.left_on_selection);
+        self.join.asof_by.iter_mut().for_each(rename);
+        rename(&mut self.sort.selection);
+        self.sort
+            .keys
+            .iter_mut()
+            .for_each(|k| rename(&mut k.column));
+        self.select_columns.selected.iter_mut().for_each(rename);
+        self.drop_nulls.subset.iter_mut().for_each(rename);
+        rename(&mut self.fill_nulls.column);
+        self.distinct.subset.iter_mut().for_each(rename);
+        rename(&mut self.replace.column);
+        rename(&mut self.string_op.column);
+        rename(&mut self.extract.column);
+        self.concat_columns.columns.iter_mut().for_each(rename);
+        rename(&mut self.split.column);
+        rename(&mut self.anonymize.column);
+        rename(&mut self.window.column);
+        self.window.partition_by.iter_mut().for_each(rename);
+        rename(&mut self.rolling.column);
+        rename(&mut self.rolling.by);
+        rename(&mut self.change.column);
+        self.change.partition_by.iter_mut().for_each(rename);
+        rename(&mut self.rank.column);
+        self.rank.partition_by.iter_mut().for_each(rename);
+        rename(&mut self.bin.column);
+        self.one_hot.columns.iter_mut().for_each(rename);
+        rename(&mut self.label_encode.column);
+        rename(&mut self.parse_dates.column);
+        rename(&mut self.date_parts.column);
+        rename(&mut self.time_zone.column);
+        rename(&mut self.upsample.column);
+        self.upsample.by.iter_mut().for_each(rename);
+        for builder in [
+            &mut self.filter.builder,
+            &mut self.aggregate.builder,
+            &mut self.formula.builder,
+        ] {
+            rename(&mut builder.column);
+            for step in &mut builder.steps {
+                if let Operand::Column(c) = &mut step.operand {
+                    rename(c);
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn filter_dataframe(
-        &mut self,
-        df: DataFrame,
-        column: &str,
-        operation: &FilterOps,
-        value: &str,
+        &self,
+        mut df: LazyFrame,
+        group: &FilterGroup,
     ) -> Result<DataFrame, PolarsError> {
-        let parsed_number = value.parse::<f64>().unwrap_or_default();
-        let parsed_string = value.parse::<String>().unwrap_or_default();
-        match operation {
-            FilterOps::EqualNum => df
-                .lazy()
-                .filter(col(column).eq(lit(parsed_number)))
-                .collect(),
-            FilterOps::EqualStr => df
-                .lazy()
-                .filter(col(column).eq(lit(parsed_string)))
-                .collect(),
-            FilterOps::GreaterThan => df
-                .lazy()
-                .filter(col(column).gt(lit(parsed_number)))
-                .collect(),
-            FilterOps::GreaterEqualThan => df
-                .lazy()
-                .filter(col(column).gt_eq(lit(parsed_number)))
-                .collect(),
-            FilterOps::LowerThan => df
-                .lazy()
-                .filter(col(column).lt(lit(parsed_number)))
-                .collect(),
-            FilterOps::LowerEqualThan => df
-                .lazy()
-                .filter(col(column).lt_eq(lit(parsed_number)))
-                .collect(),
-            FilterOps::IsNull => df.lazy().filter(col(column).is_null()).collect(),
-            FilterOps::IsNotNull => df.lazy().filter(col(column).is_not_null()).collect(),
+        let schema = df.schema()?;
+        df.filter(group.expr(&schema)?).collect()
+    }
+
+    /// Runs the filter set in the Filter section, or picked in the data view.
+    fn apply_filter(&mut self) {
+        match self.filter_dataframe(self.lazy_frame(), &self.filter.root) {
+            Ok(df) => {
+                self.filter.filtered_data = Some(df);
+                self.filter.error = None;
+            }
+            Err(e) => self.filter.error = Some(e.to_string()),
         }
     }
 
     pub fn aggregate_dataframe(
         &mut self,
-        df: DataFrame,
+        df: LazyFrame,
         groupby: &Vec<&str>,
         aggcols: &Vec<&str>,
-        aggfunc: &AggFunc,
+        aggfuncs: &[AggFunc],
+        percentile: f64,
+        expr: Option<(String, Expr)>,
     ) -> Result<DataFrame, PolarsError> {
-        match aggfunc {
-            AggFunc::Count => df
-                .lazy()
-                .group_by(groupby)
-                .agg([cols(aggcols).count()])
-                .collect(),
-            AggFunc::Sum => df
-                .lazy()
-                .group_by(groupby)
-                .agg([cols(aggcols).sum()])
-                .collect(),
-            AggFunc::Mean => df
-                .lazy()
-                .group_by(groupby)
-                .agg([cols(aggcols).mean()])
-                .collect(),
-            AggFunc::Median => df
-                .lazy()
-                .group_by(groupby)
-                .agg([cols(aggcols).median()])
-                .collect(),
-            AggFunc::Min => df
-                .lazy()
-                .group_by(groupby)
-                .agg([cols(aggcols).min()])
-                .collect(),
-            AggFunc::Max => df
-                .lazy()
-                .group_by(groupby)
-                .agg([cols(aggcols).max()])
-                .collect(),
+        if aggfuncs.is_empty() {
+            polars_bail!(ComputeError: "pick at least one metric");
+        }
+        let mut aggs = Vec::new();
+        for aggfunc in aggfuncs {
+            let suffix = match aggfuncs.len() {
+                1 => String::new(),
+                _ => format!("_{}", aggfunc.suffix(percentile)),
+            };
+            if !aggcols.is_empty() || expr.is_none() {
+                aggs.push(
+                    aggfunc
+                        .apply(cols(aggcols), percentile)
+                        .name()
+                        .suffix(&suffix),
+                );
+            }
+            if let Some((name, e)) = &expr {
+                aggs.push(
+                    aggfunc
+                        .apply(e.clone(), percentile)
+                        .alias(&format!("{}{}", name, suffix)),
+                );
+            }
+        }
+        df.group_by(groupby).agg(aggs).collect()
+    }
+    /// Puts the result of a transformation in place of the data, or in a new container named
+    /// after the transformation. Returns the error of a failed transformation.
+    fn apply_result(
+        &mut self,
+        name: &str,
+        result: PolarsResult<DataFrame>,
+        inplace: bool,
+    ) -> Option<String> {
+        match result {
+            Ok(df) if inplace => self.set_data(df),
+            Ok(df) => self.new_frames.push((name.to_string(), df)),
+            Err(e) => return Some(e.to_string()),
         }
+        None
     }
+
     pub fn join_dataframe(
         &mut self,
         container: &mut DataFrameContainer,
@@ -130,15 +408,22 @@ impl DataFrameContainer {
         join_vec: &Vec<HashMap<String, DataFrameContainer>>,
     ) {
         if !container.join.df_selection.is_empty() {
-            let join_df = get_container(&join_vec, &container.join.df_selection);
+            let join_df = get_container(join_vec, &container.join.df_selection);
             if let Some(j_df) = join_df {
-                let df = &container.data;
-                let joined_df = df.join(
-                    &j_df.data,
-                    [&container.join.left_on_selection],
-                    [&container.join.right_on_selection],
-                    JoinArgs::new(container.join.how.clone()),
-                );
+                // a cross join has a row for each pair of rows, large ones are confirmed first.
+                let rows = container.shape.0.saturating_mul(j_df.shape.0);
+                if container.join.how == JoinType::Cross
+                    && rows > CROSS_JOIN_CONFIRM_ROWS
+                    && !std::mem::take(&mut container.join.confirmed)
+                {
+                    container.join.cross_rows = Some(rows);
+                    container.join.join = false;
+                    return;
+                }
+                let joined_df = container
+                    .join
+                    .apply(container.lazy_frame(), j_df.lazy_frame());
+                container.join.error = joined_df.as_ref().err().map(|e| e.to_string());
                 if let Ok(joined) = joined_df {
                     let joined_title = format!("joined_{}{}", container.title, &frame_vec.len());
                     let joined_container = DataFrameContainer::new(joined.clone(), &joined_title);
@@ -150,10 +435,7 @@ impl DataFrameContainer {
                             // cleanup. set original filtered data back to None
                             container.filter.filtered_data = None;
                         }
-                        true => {
-                            container.data = joined.clone();
-                            container.shape = joined.shape();
-                        }
+                        true => container.set_data(joined),
                     }
                 }
                 container.join.join = false;
@@ -162,6 +444,67 @@ impl DataFrameContainer {
             }
         }
     }
+    /// Appends the rows, or columns, of the container picked in the Concat section.
+    pub fn concat_dataframe(&mut self, other: &DataFrameContainer) {
+        let result = self.concat.apply(self.lazy_frame(), other.lazy_frame());
+        self.concat.error = self.apply_result("concat", result, self.concat.inplace);
+    }
+
+    /// Writes the full data (collecting it first in lazy mode) in the given format.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_file(&self, path: &std::path::Path, format: &ExportFormat) -> Result<(), String> {
+        let mut df = self.lazy_frame().collect().map_err(|e| e.to_string())?;
+        let bytes = self
+            .export
+            .to_bytes(&mut df, format)
+            .map_err(|e| e.to_string())?;
+        std::fs::write(path, bytes).map_err(|e| e.to_string())
+    }
+
+    /// Replaces a value of the data with the text typed in the data view, parsed to the
+    /// column type.
+    fn edit_cell(&mut self, row: usize, column: &str, text: &str) -> PolarsResult<()> {
+        if self.lazy.is_some() {
+            polars_bail!(ComputeError: "collect the data before editing it");
+        }
+        let series = self.data.column(column)?;
+        let mut value = parse_value(text, series.dtype())?;
+        value.rename(column);
+        let mut edited = series.slice(0, row);
+        edited.append(&value)?;
+        edited.append(&series.slice(row as i64 + 1, series.len()))?;
+        let mut df = self.data.clone();
+        df.replace(column, edited)?;
+        self.set_data(df);
+        Ok(())
+    }
+
+    /// The first, last or randomly sampled rows of the data.
+    fn peek_data(&self, peek: &Peek, seed: u64) -> PolarsResult<DataFrame> {
+        let n = self.peek_rows as IdxSize;
+        match peek {
+            Peek::Head => self.lazy_frame().limit(n).collect(),
+            Peek::Tail => self.lazy_frame().tail(n).collect(),
+            Peek::Sample => sample_rows(&self.lazy_frame().collect()?, self.peek_rows, seed),
+        }
+    }
+
+    /// Serializes the full data, or some of the rows of the data view, for the clipboard.
+    fn copy_text(&self, markdown: bool, rows: CopyRows) -> PolarsResult<String> {
+        let mut df = match rows {
+            CopyRows::All => self.lazy_frame().collect()?,
+            CopyRows::Visible => self.view.rows(&self.data, self.view.visible_rows.clone())?,
+            CopyRows::Selected => self.view.selected(&self.data)?,
+        };
+        match markdown {
+            true => Ok(export::to_markdown(&df)),
+            false => {
+                let bytes = self.export.to_bytes(&mut df, &ExportFormat::Csv)?;
+                Ok(String::from_utf8_lossy(&bytes).to_string())
+            }
+        }
+    }
+
     pub fn show(&mut self, ctx: &egui::Context) {
         let window = Window::new(format!("🗖 {}", &self.title));
         let mut is_open = std::mem::take(&mut self.is_open); // temporary move is_open out of self
@@ -177,6 +520,43 @@ impl DataFrameContainer {
         self.is_open = is_open; // put is_open back on self.
     }
 
+    /// Source file row: watching for changes and reloading once the file changed on disk.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn show_source(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.label("Source: ");
+        ui.horizontal(|ui| {
+            let mut watch = self.watcher.is_some();
+            if ui.checkbox(&mut watch, "Watch for changes").changed() {
+                self.watcher = None;
+                if let Some(source) = self.source.as_ref().filter(|_| watch) {
+                    match FileWatcher::new(&source.path, ctx) {
+                        Ok(watcher) => self.watcher = Some(watcher),
+                        Err(e) => log::error!("Could not watch {}: {}", source.path.display(), e),
+                    }
+                }
+            }
+            if let Some(n_rows) = self.source.as_ref().and_then(|s| s.n_rows) {
+                ui.label(format!("first {} rows", n_rows));
+                if ui.button("Load full file").clicked() {
+                    if let Some(source) = self.source.as_mut() {
+                        source.n_rows = None;
+                    }
+                    if let Err(e) = self.reload() {
+                        log::error!("Could not load {}: {}", self.title, e);
+                    }
+                }
+            }
+            if self.watcher.as_ref().is_some_and(|w| w.changed()) {
+                ui.colored_label(ui.visuals().warn_fg_color, "Changed on disk");
+                if ui.button("Reload").clicked() {
+                    if let Err(e) = self.reload() {
+                        log::error!("Could not reload {}: {}", self.title, e);
+                    }
+                }
+            }
+        });
+    }
+
     fn show_content(&mut self, ctx: &egui::Context, ui: &mut egui::Ui) {
         Grid::new("main_grid")
             .num_columns(2)
@@ -184,17 +564,127 @@ impl DataFrameContainer {
             .striped(true)
             .show(ui, |ui| {
                 ui.label("Shape: ");
-                ui.label(String::from(format!("{:?}", &self.shape)));
+                ui.label(format!("{:?}", &self.shape));
                 ui.end_row();
+                if let Some(source) = self.lazy.clone() {
+                    ui.label("Lazy: ");
+                    ui.horizontal(|ui| {
+                        ui.label(format!("showing first {} rows", self.data.height()));
+                        if ui.button("Collect").clicked() {
+                            match source.0.collect() {
+                                Ok(df) => self.set_data(df),
+                                Err(e) => log::error!("Could not collect {}: {}", self.title, e),
+                            }
+                        }
+                    });
+                    ui.end_row();
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if self.source.is_some() {
+                    self.show_source(ctx, ui);
+                    ui.end_row();
+                }
                 ui.label("Data: ");
                 let btn = ui.button("View");
                 if btn.clicked() {
                     self.data_display = !&self.data_display;
                 }
                 if self.data_display {
+                    let mut copy = None;
                     Window::new(format!("{}{}", String::from("Data: "), &self.title))
                         .open(&mut self.data_display)
-                        .show(ctx, |ui| display_dataframe(&self.data, ui));
+                        .show(ctx, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.menu_button("Copy", |ui| {
+                                    for (label, markdown, rows) in [
+                                        ("All rows as CSV", false, CopyRows::All),
+                                        ("Visible rows as CSV", false, CopyRows::Visible),
+                                        ("All rows as Markdown", true, CopyRows::All),
+                                        ("Visible rows as Markdown", true, CopyRows::Visible),
+                                    ] {
+                                        if ui.button(label).clicked() {
+                                            copy = Some((markdown, rows));
+                                            ui.close_menu();
+                                        }
+                                    }
+                                });
+                                if !self.view.selected_rows.is_empty() {
+                                    ui.menu_button("Selected rows", |ui| {
+                                        if ui.button("Copy as CSV").clicked() {
+                                            copy = Some((false, CopyRows::Selected));
+                                            ui.close_menu();
+                                        }
+                                        if ui.button("Copy as Markdown").clicked() {
+                                            copy = Some((true, CopyRows::Selected));
+                                            ui.close_menu();
+                                        }
+                                        if ui.button("New DataFrame").clicked() {
+                                            match self.view.selected(&self.data) {
+                                                Ok(df) => self
+                                                    .new_frames
+                                                    .push((String::from("selected"), df)),
+                                                Err(e) => log::error!(
+                                                    "Could not take the selected rows: {}",
+                                                    e
+                                                ),
+                                            }
+                                            ui.close_menu();
+                                        }
+                                    });
+                                }
+                            });
+                            self.view.show(&self.data, ui);
+                        });
+                    if let Some((column, operation, value)) = self.view.take_filter() {
+                        self.filter.root =
+                            FilterGroup::single(FilterCondition::new(column, operation, value));
+                        self.apply_filter();
+                    }
+                    if let Some((row, column, text)) = self.view.take_edit() {
+                        if let Err(e) = self.edit_cell(row, &column, &text) {
+                            self.view.edit_error = Some(e.to_string());
+                        }
+                    }
+                    if let Some((markdown, rows)) = copy {
+                        match self.copy_text(markdown, rows) {
+                            Ok(text) => ctx.copy_text(text),
+                            Err(e) => log::error!("Could not copy {}: {}", self.title, e),
+                        }
+                    }
+                }
+                ui.end_row();
+                ui.label("Peek: ");
+                ui.horizontal(|ui| {
+                    ui.add(
+                        DragValue::new(&mut self.peek_rows)
+                            .range(1..=100_000)
+                            .suffix(" rows"),
+                    );
+                    for (label, peek) in [
+                        ("Head", Peek::Head),
+                        ("Tail", Peek::Tail),
+                        ("Sample", Peek::Sample),
+                    ] {
+                        if ui.button(label).clicked() {
+                            let seed = ctx.input(|i| i.time).to_bits();
+                            match self.peek_data(&peek, seed) {
+                                Ok(df) => {
+                                    self.peek = Some((format!("{} {}", label, self.peek_rows), df))
+                                }
+                                Err(e) => log::error!("Could not peek at {}: {}", self.title, e),
+                            }
+                        }
+                    }
+                });
+                if let Some((label, df)) = &self.peek {
+                    let mut is_open = true;
+                    Window::new(format!("{}: {}", label, &self.title))
+                        .id(egui::Id::new(("peek", &self.title)))
+                        .open(&mut is_open)
+                        .show(ctx, |ui| display_dataframe(df, ui));
+                    if !is_open {
+                        self.peek = None;
+                    }
                 }
                 ui.end_row();
                 ui.label("Data Types:");
@@ -202,21 +692,57 @@ impl DataFrameContainer {
                     self.show_datatypes = !self.show_datatypes;
                 }
                 if self.show_datatypes {
-                    let dtypes: Vec<String> = self
-                        .data
-                        .dtypes()
-                        .to_vec()
-                        .iter()
-                        .map(|d| d.to_string())
-                        .collect();
-                    let dtypes_df = df!(
-                        "Columns" => &self.columns,
-                        "Dtype" => dtypes.to_vec()
-                    )
-                    .unwrap_or_default();
+                    let dtypes = self.data.dtypes();
+                    let cast = &mut self.cast;
+                    let mut apply = false;
                     Window::new(format!("{}{}", String::from("Data Types: "), &self.title))
                         .open(&mut self.show_datatypes)
-                        .show(ctx, |ui| display_dataframe(&dtypes_df, ui));
+                        .show(ctx, |ui| {
+                            Grid::new("dtypes_grid").striped(true).show(ui, |ui| {
+                                ui.strong("Columns");
+                                ui.strong("Dtype");
+                                ui.strong("Cast to");
+                                ui.end_row();
+                                for (column, dtype) in self.columns.iter().zip(&dtypes) {
+                                    ui.label(column);
+                                    ui.label(dtype.to_string());
+                                    let target = cast.targets.get(column).unwrap_or(dtype);
+                                    let mut selected = target.clone();
+                                    ComboBox::from_id_source(("cast", column))
+                                        .selected_text(target.to_string())
+                                        .show_ui(ui, |ui| {
+                                            for option in CAST_TYPES {
+                                                let label = option.to_string();
+                                                ui.selectable_value(&mut selected, option, label);
+                                            }
+                                        });
+                                    if &selected == dtype {
+                                        cast.targets.remove(column);
+                                        cast.errors.remove(column);
+                                    } else {
+                                        cast.targets.insert(column.clone(), selected);
+                                    }
+                                    if let Some(error) = cast.errors.get(column) {
+                                        ui.colored_label(ui.visuals().error_fg_color, error);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                            let pending = !cast.targets.is_empty();
+                            if ui
+                                .add_enabled(pending, egui::Button::new("Apply casts"))
+                                .clicked()
+                            {
+                                apply = true;
+                            }
+                        });
+                    if apply {
+                        let lf = self.lazy_frame();
+                        match self.cast.apply(lf) {
+                            Ok(df) => self.set_data(df),
+                            Err(e) => log::error!("Could not cast {}: {}", self.title, e),
+                        }
+                    }
                 }
                 ui.end_row();
             });
@@ -227,71 +753,39 @@ impl DataFrameContainer {
                 ui.radio_value(&mut self.filter.inplace, false, "New");
                 ui.radio_value(&mut self.filter.inplace, true, "In Place");
             });
-            ui.horizontal(|ui| {
-                ComboBox::from_label("is")
-                    .selected_text(&self.filter.column)
-                    .show_ui(ui, |ui| {
-                        for col in &self.columns {
-                            ui.selectable_value(&mut self.filter.column, col.to_owned(), col);
-                        }
-                    });
-                ComboBox::from_label("than/to")
-                    .selected_text(format!("{:?}", &self.filter.operation))
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::EqualNum,
-                            "EqualNum",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::EqualStr,
-                            "EqualStr",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::GreaterThan,
-                            "GreaterThan",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::GreaterEqualThan,
-                            "GreaterEqualThan",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::LowerThan,
-                            "LowerThan",
-                        );
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::LowerEqualThan,
-                            "LowerEqualThan",
-                        );
-                        ui.selectable_value(&mut self.filter.operation, FilterOps::IsNull, "Null");
-                        ui.selectable_value(
-                            &mut self.filter.operation,
-                            FilterOps::IsNotNull,
-                            "IsNotNull",
-                        );
-                    });
-                ui.add(TextEdit::singleline(&mut self.filter.value).desired_width(100.0));
+            let data = &self.data;
+            self.filter
+                .root
+                .show(egui::Id::new("filter"), &self.columns, data, ui);
+            if ui.button("Filter").clicked() {
+                self.apply_filter();
+            }
+            ui.collapsing("Expression", |ui| {
+                self.filter.builder.show("filter_expr", &self.columns, ui);
                 if ui.button("Filter").clicked() {
-                    let f_df = self.filter_dataframe(
-                        self.data.clone(),
-                        &self.filter.column.clone(),
-                        &self.filter.operation.clone(),
-                        &self.filter.value.clone(),
-                    );
-                    if f_df.is_ok() {
-                        self.filter.filtered_data = f_df.ok();
-                    } else {
-                        self.data = self.data.clone()
-                    };
+                    let filtered = self
+                        .filter
+                        .builder
+                        .build()
+                        .and_then(|expr| self.lazy_frame().filter(expr).collect());
+                    match filtered {
+                        Ok(df) => {
+                            self.filter.filtered_data = Some(df);
+                            self.filter.error = None;
+                        }
+                        Err(e) => self.filter.error = Some(e.to_string()),
+                    }
                 }
-            })
+            });
+            if let Some(error) = &self.filter.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
         });
         ui.collapsing("Aggregate", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.aggregate.inplace, false, "New");
+                ui.radio_value(&mut self.aggregate.inplace, true, "In Place");
+            });
             ui.label("Group by:");
             ui.horizontal(|ui| {
                 ComboBox::new("Grp", "")
@@ -305,16 +799,15 @@ impl DataFrameContainer {
                             );
                         }
                     });
-                if ui.button("Add").clicked() {
-                    if !self
+                if ui.button("Add").clicked()
+                    && !self
                         .aggregate
                         .groupby
                         .contains(&self.aggregate.grp_selection)
-                    {
-                        self.aggregate
-                            .groupby
-                            .push(self.aggregate.grp_selection.clone());
-                    }
+                {
+                    self.aggregate
+                        .groupby
+                        .push(self.aggregate.grp_selection.clone());
                 }
             });
             ui.label(format!("Selected: {:?}", &self.aggregate.groupby));
@@ -331,51 +824,102 @@ impl DataFrameContainer {
                             );
                         }
                     });
-                if ui.button("Add").clicked() {
-                    if !self
+                if ui.button("Add").clicked()
+                    && !self
                         .aggregate
                         .aggcols
                         .contains(&self.aggregate.agg_selection)
-                    {
-                        self.aggregate
-                            .aggcols
-                            .push(self.aggregate.agg_selection.clone());
-                    }
+                {
+                    self.aggregate
+                        .aggcols
+                        .push(self.aggregate.agg_selection.clone());
+                }
+                if ui
+                    .button("All numeric")
+                    .on_hover_text("Every numeric column that isn't grouped by")
+                    .clicked()
+                {
+                    self.aggregate.aggcols = self
+                        .columns
+                        .iter()
+                        .zip(self.data.dtypes())
+                        .filter(|(c, dtype)| {
+                            dtype.is_numeric() && !self.aggregate.groupby.contains(c)
+                        })
+                        .map(|(c, _)| c.clone())
+                        .collect();
+                }
+                if ui.button("Clear").clicked() {
+                    self.aggregate.aggcols.clear();
                 }
             });
             ui.label(format!("Selected: {:?}", &self.aggregate.aggcols));
-            ui.label("Metric: ");
             ui.horizontal(|ui| {
-                ui.radio_value(&mut self.aggregate.aggfunc, AggFunc::Count, "Count");
-                ui.radio_value(&mut self.aggregate.aggfunc, AggFunc::Sum, "Sum");
-                ui.radio_value(&mut self.aggregate.aggfunc, AggFunc::Mean, "Mean");
+                ui.checkbox(&mut self.aggregate.use_expr, "Expression:");
+                ui.add_enabled(
+                    self.aggregate.use_expr,
+                    TextEdit::singleline(&mut self.aggregate.expr_name).desired_width(100.0),
+                );
             });
-            ui.horizontal(|ui| {
-                ui.radio_value(&mut self.aggregate.aggfunc, AggFunc::Median, "Median");
-                ui.radio_value(&mut self.aggregate.aggfunc, AggFunc::Min, "Min");
-                ui.radio_value(&mut self.aggregate.aggfunc, AggFunc::Max, "Max");
+            if self.aggregate.use_expr {
+                self.aggregate
+                    .builder
+                    .show("aggregate_expr", &self.columns, ui);
+            }
+            ui.label("Metric: ");
+            ui.horizontal_wrapped(|ui| {
+                for aggfunc in AggFunc::ALL {
+                    let mut checked = self.aggregate.aggfuncs.contains(&aggfunc);
+                    if ui.checkbox(&mut checked, aggfunc.label()).changed() {
+                        match checked {
+                            true => self.aggregate.aggfuncs.push(aggfunc),
+                            false => self.aggregate.aggfuncs.retain(|f| f != &aggfunc),
+                        }
+                    }
+                }
             });
+            if self.aggregate.aggfuncs.contains(&AggFunc::Quantile) {
+                ui.horizontal(|ui| {
+                    ui.label("Percentile:");
+                    ui.add(
+                        DragValue::new(&mut self.aggregate.percentile)
+                            .range(0.0..=100.0)
+                            .speed(0.5),
+                    );
+                });
+            }
 
             if ui.button("Aggregate").clicked() {
-                self.aggregate.display = true;
                 let binding = self.aggregate.groupby.clone();
                 let binding2 = self.aggregate.aggcols.clone();
-                let binding3 = self.aggregate.aggfunc.clone();
+                let binding3 = self.aggregate.aggfuncs.clone();
                 let str_gp: &Vec<&str> = &binding.iter().map(|s| s.as_str()).collect();
                 let str_agg: &Vec<&str> = &binding2.iter().map(|s| s.as_str()).collect();
 
-                let aggdf = self.aggregate_dataframe(self.data.clone(), str_gp, str_agg, &binding3);
-                if let Ok(aggregated) = aggdf {
-                    self.aggregate.aggdata = Some(aggregated);
-                }
+                let expr = match self.aggregate.use_expr {
+                    true => self
+                        .aggregate
+                        .builder
+                        .build()
+                        .map(|e| Some((self.aggregate.expr_name.clone(), e))),
+                    false => Ok(None),
+                };
+                let percentile = self.aggregate.percentile;
+                let aggdf = expr.and_then(|expr| {
+                    self.aggregate_dataframe(
+                        self.lazy_frame(),
+                        str_gp,
+                        str_agg,
+                        &binding3,
+                        percentile,
+                        expr,
+                    )
+                });
+                let inplace = self.aggregate.inplace;
+                self.aggregate.error = self.apply_result("aggregated", aggdf, inplace);
             }
-            if self.aggregate.display {
-                let binding = self.aggregate.aggdata.clone().unwrap();
-                Window::new(format!("{}{}", String::from("Aggregation: "), &self.title))
-                    .open(&mut self.aggregate.display)
-                    .show(ctx, |ui| {
-                        display_dataframe(&binding.clone(), ui);
-                    });
+            if let Some(error) = &self.aggregate.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
             }
         });
         ui.collapsing("Join", |ui| {
@@ -409,10 +953,121 @@ impl DataFrameContainer {
                 ui.radio_value(&mut self.join.how, JoinType::Left, "Left");
                 ui.radio_value(&mut self.join.how, JoinType::Full, "Full");
                 ui.radio_value(&mut self.join.how, JoinType::Cross, "Cross");
+                ui.radio_value(
+                    &mut self.join.how,
+                    JoinType::AsOf(AsOfOptions::default()),
+                    "As-of",
+                );
+            });
+            if self.join.is_asof() {
+                ui.horizontal(|ui| {
+                    ui.label("Match the");
+                    ui.radio_value(
+                        &mut self.join.asof_strategy,
+                        AsofStrategy::Backward,
+                        "Previous",
+                    );
+                    ui.radio_value(&mut self.join.asof_strategy, AsofStrategy::Forward, "Next");
+                    ui.radio_value(
+                        &mut self.join.asof_strategy,
+                        AsofStrategy::Nearest,
+                        "Nearest",
+                    );
+                    ui.label("key");
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Tolerance:");
+                    ui.add(
+                        TextEdit::singleline(&mut self.join.tolerance)
+                            .hint_text("none")
+                            .desired_width(80.0),
+                    )
+                    .on_hover_text("A number, or a duration like 5m or 1h30m for dates");
+                });
+                ui.label("Also match by:");
+                column_checkboxes("asof_by", &self.columns, &mut self.join.asof_by, ui);
+            }
+            ui.horizontal(|ui| {
+                ui.label("Duplicate columns:");
+                ui.radio_value(&mut self.join.drop_duplicates, false, "Suffix");
+                ui.radio_value(&mut self.join.drop_duplicates, true, "Drop");
+                if !self.join.drop_duplicates {
+                    ui.add(TextEdit::singleline(&mut self.join.suffix).desired_width(80.0));
+                }
             });
             if ui.button("Join").clicked() {
                 self.join.join = !self.join.join
             }
+            if let Some(error) = &self.join.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            if let Some(rows) = self.join.cross_rows {
+                Window::new(format!("Cross join: {}", &self.title))
+                    .collapsible(false)
+                    .resizable(false)
+                    .show(ctx, |ui| {
+                        ui.label(format!(
+                            "The cross join with '{}' will have {} rows, it can take a long \
+                            time and a lot of memory.",
+                            self.join.df_selection, rows
+                        ));
+                        ui.horizontal(|ui| {
+                            if ui.button("Join anyway").clicked() {
+                                self.join.cross_rows = None;
+                                self.join.confirmed = true;
+                                self.join.join = true;
+                            }
+                            if ui.button("Cancel").clicked() {
+                                self.join.cross_rows = None;
+                            }
+                        });
+                    });
+            }
+        });
+        ui.collapsing("Concat", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.concat.inplace, false, "New");
+                ui.radio_value(&mut self.concat.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.concat.horizontal, false, "Rows");
+                ui.radio_value(&mut self.concat.horizontal, true, "Columns");
+            });
+            ui.horizontal(|ui| {
+                ui.label(match self.concat.horizontal {
+                    true => "Add columns of",
+                    false => "Append rows of",
+                });
+                ComboBox::new("concat_df", "")
+                    .selected_text(&self.concat.df_selection)
+                    .show_ui(ui, |ui| {
+                        for title in &self.concat.df_list {
+                            ui.selectable_value(
+                                &mut self.concat.df_selection,
+                                title.to_owned(),
+                                title,
+                            );
+                        }
+                    });
+            });
+            if self.concat.horizontal {
+                ui.horizontal(|ui| {
+                    ui.label("Suffix for duplicate names:");
+                    ui.add(TextEdit::singleline(&mut self.concat.suffix).desired_width(80.0));
+                });
+            }
+            if ui
+                .add_enabled(
+                    !self.concat.df_selection.is_empty(),
+                    egui::Button::new("Concat"),
+                )
+                .clicked()
+            {
+                self.concat.concat = true;
+            }
+            if let Some(error) = &self.concat.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
         });
         ui.collapsing("Melt", |ui| {
             ui.label("ID Vars: ");
@@ -424,32 +1079,58 @@ impl DataFrameContainer {
                             ui.selectable_value(&mut self.melt.id_selection, col.to_owned(), col);
                         }
                     });
-                if ui.button("Add").clicked() {
-                    if !self.melt.id_vars.contains(&self.melt.id_selection) {
-                        self.melt.id_vars.push(self.melt.id_selection.clone());
-                    }
+                if ui.button("Add").clicked()
+                    && !self.melt.id_vars.contains(&self.melt.id_selection)
+                {
+                    self.melt.id_vars.push(self.melt.id_selection.clone());
                 }
             });
             ui.label(format!("Selected: {:?}", &self.melt.id_vars));
             ui.label("Value Vars: ");
-            ui.horizontal(|ui| {
-                ComboBox::new("Valvars", "")
-                    .selected_text(&self.melt.val_selection)
-                    .show_ui(ui, |ui| {
-                        for col in &self.columns {
-                            ui.selectable_value(&mut self.melt.val_selection, col.to_owned(), col);
-                        }
-                    });
-                if ui.button("Add").clicked() {
-                    if !self.melt.value_vars.contains(&self.melt.val_selection) {
+            ui.checkbox(&mut self.melt.all_remaining, "All remaining columns");
+            if !self.melt.all_remaining {
+                ui.horizontal(|ui| {
+                    ComboBox::new("Valvars", "")
+                        .selected_text(&self.melt.val_selection)
+                        .show_ui(ui, |ui| {
+                            for col in &self.columns {
+                                ui.selectable_value(
+                                    &mut self.melt.val_selection,
+                                    col.to_owned(),
+                                    col,
+                                );
+                            }
+                        });
+                    if ui.button("Add").clicked()
+                        && !self.melt.value_vars.contains(&self.melt.val_selection)
+                    {
                         self.melt.value_vars.push(self.melt.val_selection.clone());
                     }
-                }
+                });
+                ui.label(format!("Selected: {:?}", &self.melt.value_vars));
+            }
+            Grid::new("melt_names").num_columns(2).show(ui, |ui| {
+                ui.label("Variable column:");
+                ui.add(
+                    TextEdit::singleline(&mut self.melt.variable_name)
+                        .hint_text("variable")
+                        .desired_width(100.0),
+                );
+                ui.end_row();
+                ui.label("Value column:");
+                ui.add(
+                    TextEdit::singleline(&mut self.melt.value_name)
+                        .hint_text("value")
+                        .desired_width(100.0),
+                );
+                ui.end_row();
             });
-            ui.label(format!("Selected: {:?}", &self.melt.value_vars));
             if ui.button("Melt").clicked() {
                 self.melt.display = true;
-                let melted_df = self.data.unpivot(&self.melt.id_vars, &self.melt.value_vars);
+                let melted_df = self
+                    .lazy_frame()
+                    .unpivot(self.melt.args(&self.columns))
+                    .collect();
                 if melted_df.is_ok() {
                     self.melt.meltdata = melted_df.ok();
                 }
@@ -463,5 +1144,1353 @@ impl DataFrameContainer {
                     });
             }
         });
-    }
+        ui.collapsing("Pivot", |ui| {
+            ui.label("Index:");
+            column_checkboxes("pivot_index", &self.columns, &mut self.pivot.index, ui);
+            Grid::new("pivot_grid").num_columns(2).show(ui, |ui| {
+                ui.label("Columns from:");
+                ComboBox::new("pivot_on", "")
+                    .selected_text(&self.pivot.on)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.pivot.on, col.to_owned(), col);
+                        }
+                    });
+                ui.end_row();
+                ui.label("Values:");
+                ComboBox::new("pivot_values", "")
+                    .selected_text(&self.pivot.values)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.pivot.values, col.to_owned(), col);
+                        }
+                    });
+                ui.end_row();
+                ui.label("Aggregate:");
+                ComboBox::new("pivot_agg", "")
+                    .selected_text(self.pivot.aggfunc.label())
+                    .show_ui(ui, |ui| {
+                        for func in PivotFunc::ALL {
+                            ui.selectable_value(&mut self.pivot.aggfunc, func, func.label());
+                        }
+                    });
+                ui.end_row();
+            });
+            if ui.button("Pivot").clicked() {
+                let pivoted = self.pivot.apply(self.lazy_frame());
+                self.pivot.error = self.apply_result("pivot", pivoted, false);
+            }
+            if let Some(error) = &self.pivot.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Transpose", |ui| {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.transpose.use_header, "Header from");
+                ui.add_enabled_ui(self.transpose.use_header, |ui| {
+                    ComboBox::new("transpose_header", "")
+                        .selected_text(&self.transpose.header)
+                        .show_ui(ui, |ui| {
+                            for col in &self.columns {
+                                ui.selectable_value(
+                                    &mut self.transpose.header,
+                                    col.to_owned(),
+                                    col,
+                                );
+                            }
+                        });
+                });
+            });
+            ui.horizontal(|ui| {
+                ui.label("Column names to:");
+                ui.add(TextEdit::singleline(&mut self.transpose.names).desired_width(100.0))
+                    .on_hover_text("Leave empty to drop the column names");
+            });
+            if ui.button("Transpose").clicked() {
+                let transposed = self.transpose.apply(self.lazy_frame());
+                self.transpose.error = self.apply_result("transposed", transposed, false);
+            }
+            if let Some(error) = &self.transpose.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Sort", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.sort.inplace, false, "New");
+                ui.radio_value(&mut self.sort.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("sort_by", "")
+                    .selected_text(&self.sort.selection)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.sort.selection, col.to_owned(), col);
+                        }
+                    });
+                let selection = self.sort.selection.clone();
+                if ui.button("Add").clicked()
+                    && !selection.is_empty()
+                    && !self.sort.keys.iter().any(|k| k.column == selection)
+                {
+                    self.sort.keys.push(SortKey {
+                        column: selection,
+                        descending: false,
+                        nulls_last: true,
+                    });
+                }
+            });
+            let mut remove = None;
+            Grid::new("sort_keys").striped(true).show(ui, |ui| {
+                for (i, key) in self.sort.keys.iter_mut().enumerate() {
+                    ui.label(&key.column);
+                    ui.checkbox(&mut key.descending, "Descending");
+                    ui.checkbox(&mut key.nulls_last, "Nulls last");
+                    if ui.button("✖").clicked() {
+                        remove = Some(i);
+                    }
+                    ui.end_row();
+                }
+            });
+            if let Some(i) = remove {
+                self.sort.keys.remove(i);
+            }
+            if ui.button("Sort").clicked() {
+                let sorted = self.sort.sort(self.lazy_frame());
+                self.sort.error = self.apply_result("sorted", sorted, self.sort.inplace);
+            }
+            if let Some(error) = &self.sort.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Sample", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.sample.by_fraction, false, "Rows");
+                ui.radio_value(&mut self.sample.by_fraction, true, "Fraction");
+                match self.sample.by_fraction {
+                    true => ui.add(
+                        DragValue::new(&mut self.sample.fraction)
+                            .range(0.0..=10.0)
+                            .speed(0.01),
+                    ),
+                    false => ui.add(DragValue::new(&mut self.sample.n)),
+                };
+            });
+            ui.checkbox(&mut self.sample.replacement, "With replacement");
+            ui.horizontal(|ui| {
+                let mut seeded = self.sample.seed.is_some();
+                if ui.checkbox(&mut seeded, "Seed").changed() {
+                    self.sample.seed = seeded.then_some(42);
+                }
+                if let Some(seed) = self.sample.seed.as_mut() {
+                    ui.add(DragValue::new(seed));
+                }
+            });
+            if ui.button("Sample").clicked() {
+                let seed = ui.input(|i| i.time).to_bits();
+                let sampled = self.sample.apply(self.lazy_frame(), seed);
+                self.sample.error = self.apply_result("sample", sampled, false);
+            }
+            if let Some(error) = &self.sample.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Slice", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.slice.mode, SliceMode::Head, "First");
+                ui.radio_value(&mut self.slice.mode, SliceMode::Tail, "Last");
+                ui.radio_value(&mut self.slice.mode, SliceMode::Window, "Offset");
+            });
+            ui.horizontal(|ui| match self.slice.mode {
+                SliceMode::Head | SliceMode::Tail => {
+                    ui.add(DragValue::new(&mut self.slice.n));
+                    ui.label("rows");
+                }
+                SliceMode::Window => {
+                    ui.label("From row");
+                    ui.add(DragValue::new(&mut self.slice.offset))
+                        .on_hover_text("Negative offsets count from the end");
+                    ui.label("take");
+                    ui.add(DragValue::new(&mut self.slice.length));
+                    ui.label("rows");
+                }
+            });
+            if ui.button("Slice").clicked() {
+                let sliced = self.slice.apply(self.lazy_frame());
+                self.slice.error = self.apply_result("slice", sliced, false);
+            }
+            if let Some(error) = &self.slice.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Select/Drop Columns", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.select_columns.inplace, false, "New");
+                ui.radio_value(&mut self.select_columns.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.select_columns.keep, true, "Keep");
+                ui.radio_value(&mut self.select_columns.keep, false, "Drop");
+                if ui.button("All").clicked() {
+                    self.select_columns.selected = self.columns.clone();
+                }
+                if ui.button("None").clicked() {
+                    self.select_columns.selected.clear();
+                }
+            });
+            column_checkboxes(
+                "select_columns",
+                &self.columns,
+                &mut self.select_columns.selected,
+                ui,
+            );
+            if ui.button("Apply").clicked() {
+                let result = self.select_columns.apply(self.lazy_frame());
+                let inplace = self.select_columns.inplace;
+                self.select_columns.error = self.apply_result("columns", result, inplace);
+            }
+            if let Some(error) = &self.select_columns.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Rename Columns", |ui| {
+            if self.rename.names.len() != self.columns.len() {
+                self.rename.names = self.columns.clone();
+            }
+            egui::ScrollArea::vertical()
+                .id_source("rename_columns")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    Grid::new("rename_grid").striped(true).show(ui, |ui| {
+                        for (col, name) in self.columns.iter().zip(&mut self.rename.names) {
+                            ui.label(col);
+                            ui.add(TextEdit::singleline(name).desired_width(150.0));
+                            ui.end_row();
+                        }
+                    });
+                });
+            ui.horizontal(|ui| {
+                ui.label("Prefix:");
+                ui.add(TextEdit::singleline(&mut self.rename.prefix).desired_width(60.0));
+                ui.label("Suffix:");
+                ui.add(TextEdit::singleline(&mut self.rename.suffix).desired_width(60.0));
+                if ui.button("Add").clicked() {
+                    self.rename.add_affixes();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Find:");
+                ui.add(TextEdit::singleline(&mut self.rename.find).desired_width(60.0));
+                ui.label("Replace:");
+                ui.add(TextEdit::singleline(&mut self.rename.replace).desired_width(60.0));
+                if ui.button("Replace").clicked() {
+                    self.rename.find_replace();
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Rename").clicked() {
+                    let result = self
+                        .rename
+                        .renames(&self.columns)
+                        .and_then(|renames| self.rename_columns(&renames));
+                    self.rename.error = result.err().map(|e| e.to_string());
+                }
+                if ui.button("Reset").clicked() {
+                    self.rename.names = self.columns.clone();
+                    self.rename.error = None;
+                }
+            });
+            if let Some(error) = &self.rename.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Reorder Columns", |ui| {
+            if self.reorder.order.len() != self.columns.len() {
+                self.reorder.order = self.columns.clone();
+            }
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.reorder.inplace, false, "New");
+                ui.radio_value(&mut self.reorder.inplace, true, "In Place");
+            });
+            let mut moved = None;
+            egui::ScrollArea::vertical()
+                .id_source("reorder_columns")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    Grid::new("reorder_grid").striped(true).show(ui, |ui| {
+                        let last = self.reorder.order.len().saturating_sub(1);
+                        for (i, col) in self.reorder.order.iter().enumerate() {
+                            ui.label(col);
+                            if ui.add_enabled(i > 0, egui::Button::new("⏶")).clicked() {
+                                moved = Some((i, true));
+                            }
+                            if ui.add_enabled(i < last, egui::Button::new("⏷")).clicked() {
+                                moved = Some((i, false));
+                            }
+                            ui.end_row();
+                        }
+                    });
+                });
+            if let Some((i, up)) = moved {
+                self.reorder.move_column(i, up);
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Apply").clicked() {
+                    let result = self.reorder.apply(self.lazy_frame());
+                    let inplace = self.reorder.inplace;
+                    self.reorder.error = self.apply_result("reordered", result, inplace);
+                }
+                if ui.button("Reset").clicked() {
+                    self.reorder.order = self.columns.clone();
+                    self.reorder.error = None;
+                }
+            });
+            if let Some(error) = &self.reorder.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Row Index", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.row_index.inplace, false, "New");
+                ui.radio_value(&mut self.row_index.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(TextEdit::singleline(&mut self.row_index.name).desired_width(100.0));
+                ui.label("Start at:");
+                ui.add(DragValue::new(&mut self.row_index.offset));
+            });
+            if ui.button("Add").clicked() {
+                let result = self.row_index.apply(self.lazy_frame());
+                let inplace = self.row_index.inplace;
+                self.row_index.error = self.apply_result("indexed", result, inplace);
+            }
+            if let Some(error) = &self.row_index.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("New Column", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.formula.inplace, false, "New");
+                ui.radio_value(&mut self.formula.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(TextEdit::singleline(&mut self.formula.name).desired_width(150.0));
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.formula.use_builder, false, "Formula");
+                ui.radio_value(&mut self.formula.use_builder, true, "Builder");
+            });
+            match self.formula.use_builder {
+                true => self.formula.builder.show("formula_expr", &self.columns, ui),
+                false => {
+                    ui.add(
+                        TextEdit::singleline(&mut self.formula.formula)
+                            .hint_text("price * qty")
+                            .desired_width(250.0),
+                    );
+                }
+            }
+            if ui.button("Add column").clicked() {
+                let result = self.formula.apply(self.lazy_frame());
+                let inplace = self.formula.inplace;
+                self.formula.error = self.apply_result("computed", result, inplace);
+            }
+            if let Some(error) = &self.formula.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Window", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.window.inplace, false, "New");
+                ui.radio_value(&mut self.window.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("window_func", "of")
+                    .selected_text(self.window.func.label())
+                    .show_ui(ui, |ui| {
+                        for func in WindowFunc::ALL {
+                            ui.selectable_value(&mut self.window.func, func, func.label());
+                        }
+                    });
+                ComboBox::new("window_column", "")
+                    .selected_text(&self.window.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.window.column, col.to_owned(), col);
+                        }
+                    });
+            });
+            ui.label("Over (all rows if none):");
+            column_checkboxes(
+                "window_partition",
+                &self.columns,
+                &mut self.window.partition_by,
+                ui,
+            );
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(
+                    TextEdit::singleline(&mut self.window.name)
+                        .hint_text(format!(
+                            "{}_{}",
+                            self.window.column,
+                            self.window.func.label()
+                        ))
+                        .desired_width(150.0),
+                );
+            });
+            if ui.button("Add column").clicked() {
+                let result = self.window.apply(self.lazy_frame());
+                let inplace = self.window.inplace;
+                self.window.error = self.apply_result("window", result, inplace);
+            }
+            if let Some(error) = &self.window.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Rolling", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.rolling.inplace, false, "New");
+                ui.radio_value(&mut self.rolling.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("rolling_func", "of")
+                    .selected_text(self.rolling.func.label())
+                    .show_ui(ui, |ui| {
+                        for func in RollingFunc::ALL {
+                            ui.selectable_value(&mut self.rolling.func, func, func.label());
+                        }
+                    });
+                ComboBox::new("rolling_column", "")
+                    .selected_text(&self.rolling.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.rolling.column, col.to_owned(), col);
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label("Window:");
+                ui.radio_value(&mut self.rolling.by_time, false, "Rows");
+                ui.radio_value(&mut self.rolling.by_time, true, "Duration");
+            });
+            ui.horizontal(|ui| match self.rolling.by_time {
+                true => {
+                    ui.add(
+                        TextEdit::singleline(&mut self.rolling.duration)
+                            .hint_text("7d")
+                            .desired_width(60.0),
+                    );
+                    ComboBox::new("rolling_by", "back from")
+                        .selected_text(&self.rolling.by)
+                        .show_ui(ui, |ui| {
+                            for col in &self.columns {
+                                ui.selectable_value(&mut self.rolling.by, col.to_owned(), col);
+                            }
+                        });
+                }
+                false => {
+                    ui.add(DragValue::new(&mut self.rolling.rows).range(1..=100_000));
+                    ui.label("rows");
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(
+                    TextEdit::singleline(&mut self.rolling.name)
+                        .hint_text(format!(
+                            "{}_rolling_{}",
+                            self.rolling.column,
+                            self.rolling.func.label()
+                        ))
+                        .desired_width(150.0),
+                );
+            });
+            if ui.button("Add column").clicked() {
+                let result = self.rolling.apply(self.lazy_frame());
+                let inplace = self.rolling.inplace;
+                self.rolling.error = self.apply_result("rolling", result, inplace);
+            }
+            if let Some(error) = &self.rolling.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Cumulative/Change/Shift", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.change.inplace, false, "New");
+                ui.radio_value(&mut self.change.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("change_op", "of")
+                    .selected_text(self.change.op.label())
+                    .show_ui(ui, |ui| {
+                        for op in ChangeOp::ALL {
+                            ui.selectable_value(&mut self.change.op, op, op.label());
+                        }
+                    });
+                ComboBox::new("change_column", "")
+                    .selected_text(&self.change.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.change.column, col.to_owned(), col);
+                        }
+                    });
+                if self.change.op != ChangeOp::CumSum {
+                    ui.label("periods");
+                    ui.add(DragValue::new(&mut self.change.periods));
+                }
+            });
+            if self.change.op == ChangeOp::Shift {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.change.lead, false, "Lag (rows before)");
+                    ui.radio_value(&mut self.change.lead, true, "Lead (rows after)");
+                });
+            }
+            ui.label("Within groups of (none for all rows):");
+            column_checkboxes(
+                "change_partition",
+                &self.columns,
+                &mut self.change.partition_by,
+                ui,
+            );
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                let hint = self.change.default_name();
+                ui.add(
+                    TextEdit::singleline(&mut self.change.name)
+                        .hint_text(hint)
+                        .desired_width(150.0),
+                );
+            });
+            if ui.button("Add column").clicked() {
+                let result = self.change.apply(self.lazy_frame());
+                let inplace = self.change.inplace;
+                self.change.error = self.apply_result("change", result, inplace);
+            }
+            if let Some(error) = &self.change.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Rank", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.rank.inplace, false, "New");
+                ui.radio_value(&mut self.rank.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("rank_column", "")
+                    .selected_text(&self.rank.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.rank.column, col.to_owned(), col);
+                        }
+                    });
+                ui.checkbox(&mut self.rank.descending, "Descending");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Ties:");
+                for (method, label) in RANK_METHODS {
+                    ui.radio_value(&mut self.rank.method, method, label);
+                }
+            });
+            ui.label("Within groups of (none for all rows):");
+            column_checkboxes(
+                "rank_partition",
+                &self.columns,
+                &mut self.rank.partition_by,
+                ui,
+            );
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(
+                    TextEdit::singleline(&mut self.rank.name)
+                        .hint_text(format!("{}_rank", self.rank.column))
+                        .desired_width(150.0),
+                );
+            });
+            if ui.button("Add column").clicked() {
+                let result = self.rank.apply(self.lazy_frame());
+                let inplace = self.rank.inplace;
+                self.rank.error = self.apply_result("rank", result, inplace);
+            }
+            if let Some(error) = &self.rank.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Bin", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.bin.inplace, false, "New");
+                ui.radio_value(&mut self.bin.inplace, true, "In Place");
+            });
+            ComboBox::new("bin_column", "")
+                .selected_text(&self.bin.column)
+                .show_ui(ui, |ui| {
+                    for col in &self.columns {
+                        ui.selectable_value(&mut self.bin.column, col.to_owned(), col);
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.bin.quantiles, false, "Breakpoints");
+                ui.radio_value(&mut self.bin.quantiles, true, "Quantiles");
+            });
+            ui.horizontal(|ui| match self.bin.quantiles {
+                true => {
+                    ui.add(DragValue::new(&mut self.bin.bins).range(2..=100));
+                    ui.label("bins");
+                }
+                false => {
+                    ui.add(
+                        TextEdit::singleline(&mut self.bin.breaks)
+                            .hint_text("0, 18, 65")
+                            .desired_width(150.0),
+                    );
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Labels:");
+                ui.add(
+                    TextEdit::singleline(&mut self.bin.labels)
+                        .hint_text("one per bin, optional")
+                        .desired_width(150.0),
+                );
+            });
+            ui.checkbox(
+                &mut self.bin.left_closed,
+                "Include the lower bound of each bin",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Name:");
+                ui.add(
+                    TextEdit::singleline(&mut self.bin.name)
+                        .hint_text(format!("{}_bin", self.bin.column))
+                        .desired_width(150.0),
+                );
+            });
+            if ui.button("Add column").clicked() {
+                let result = self.bin.apply(self.lazy_frame());
+                let inplace = self.bin.inplace;
+                self.bin.error = self.apply_result("binned", result, inplace);
+            }
+            if let Some(error) = &self.bin.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("One-Hot Encode", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.one_hot.inplace, false, "New");
+                ui.radio_value(&mut self.one_hot.inplace, true, "In Place");
+            });
+            column_checkboxes(
+                "one_hot_columns",
+                &self.columns,
+                &mut self.one_hot.columns,
+                ui,
+            );
+            ui.horizontal(|ui| {
+                ui.label("Prefix:");
+                ui.add(
+                    TextEdit::singleline(&mut self.one_hot.prefix)
+                        .hint_text("optional")
+                        .desired_width(100.0),
+                );
+            });
+            ui.checkbox(&mut self.one_hot.drop_first, "Drop the first value");
+            if ui.button("Encode").clicked() {
+                let result = self.one_hot.apply(self.lazy_frame());
+                let inplace = self.one_hot.inplace;
+                self.one_hot.error = self.apply_result("encoded", result, inplace);
+            }
+            if let Some(error) = &self.one_hot.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Label Encode", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.label_encode.inplace, false, "New");
+                ui.radio_value(&mut self.label_encode.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("label_encode_column", "to")
+                    .selected_text(&self.label_encode.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.label_encode.column, col.to_owned(), col);
+                        }
+                    });
+                ui.radio_value(&mut self.label_encode.codes, false, "Categorical");
+                ui.radio_value(&mut self.label_encode.codes, true, "Integer codes");
+            });
+            ui.horizontal(|ui| {
+                if ui.button("Encode").clicked() {
+                    let result = self.label_encode.apply(self.lazy_frame());
+                    let inplace = self.label_encode.inplace;
+                    let (result, mapping) = match result {
+                        Ok((df, mapping)) => (Ok(df), mapping),
+                        Err(e) => (Err(e), None),
+                    };
+                    self.label_encode.show_mapping = mapping.is_some();
+                    self.label_encode.mapping = mapping;
+                    self.label_encode.error = self.apply_result("encoded", result, inplace);
+                }
+                if self.label_encode.mapping.is_some() && ui.button("Codes").clicked() {
+                    self.label_encode.show_mapping = true;
+                }
+            });
+            if let Some(error) = &self.label_encode.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+            if let Some(mapping) = &self.label_encode.mapping {
+                Window::new(format!("Codes: {}", &self.title))
+                    .open(&mut self.label_encode.show_mapping)
+                    .show(ctx, |ui| {
+                        display_dataframe(mapping, ui);
+                    });
+            }
+        });
+        ui.collapsing("Drop Nulls", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.drop_nulls.inplace, false, "New");
+                ui.radio_value(&mut self.drop_nulls.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Null in:");
+                ui.radio_value(&mut self.drop_nulls.use_subset, false, "Any column");
+                ui.radio_value(&mut self.drop_nulls.use_subset, true, "Selected columns");
+            });
+            if self.drop_nulls.use_subset {
+                column_checkboxes(
+                    "drop_nulls_columns",
+                    &self.columns,
+                    &mut self.drop_nulls.subset,
+                    ui,
+                );
+            }
+            if ui.button("Drop").clicked() {
+                let result = self.drop_nulls.apply(self.lazy_frame());
+                let inplace = self.drop_nulls.inplace;
+                self.drop_nulls.error = self.apply_result("not_null", result, inplace);
+            }
+            if let Some(error) = &self.drop_nulls.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Fill Nulls", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.fill_nulls.inplace, false, "New");
+                ui.radio_value(&mut self.fill_nulls.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                let column = match self.fill_nulls.column.is_empty() {
+                    true => "All columns",
+                    false => self.fill_nulls.column.as_str(),
+                };
+                ComboBox::new("fill_column", "")
+                    .selected_text(column.to_string())
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut self.fill_nulls.column,
+                            String::new(),
+                            "All columns",
+                        );
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.fill_nulls.column, col.to_owned(), col);
+                        }
+                    });
+                ComboBox::new("fill_strategy", "with")
+                    .selected_text(self.fill_nulls.strategy.label())
+                    .show_ui(ui, |ui| {
+                        for strategy in FillStrategy::ALL {
+                            ui.selectable_value(
+                                &mut self.fill_nulls.strategy,
+                                strategy,
+                                strategy.label(),
+                            );
+                        }
+                    });
+                if self.fill_nulls.strategy == FillStrategy::Value {
+                    ui.add(TextEdit::singleline(&mut self.fill_nulls.value).desired_width(80.0));
+                }
+            });
+            if ui.button("Fill").clicked() {
+                let result = self.fill_nulls.apply(self.lazy_frame());
+                let inplace = self.fill_nulls.inplace;
+                self.fill_nulls.error = self.apply_result("filled", result, inplace);
+            }
+            if let Some(error) = &self.fill_nulls.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Distinct", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.distinct.inplace, false, "New");
+                ui.radio_value(&mut self.distinct.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Compare:");
+                ui.radio_value(&mut self.distinct.use_subset, false, "All columns");
+                ui.radio_value(&mut self.distinct.use_subset, true, "Selected columns");
+            });
+            if self.distinct.use_subset {
+                column_checkboxes(
+                    "distinct_columns",
+                    &self.columns,
+                    &mut self.distinct.subset,
+                    ui,
+                );
+            }
+            ui.horizontal(|ui| {
+                ui.label("Keep:");
+                ui.radio_value(&mut self.distinct.keep, UniqueKeepStrategy::First, "First");
+                ui.radio_value(&mut self.distinct.keep, UniqueKeepStrategy::Last, "Last");
+                ui.radio_value(&mut self.distinct.keep, UniqueKeepStrategy::None, "None");
+            });
+            if ui.button("Distinct").clicked() {
+                let result = self.distinct.apply(self.lazy_frame());
+                let inplace = self.distinct.inplace;
+                self.distinct.error = self.apply_result("distinct", result, inplace);
+            }
+            if let Some(error) = &self.distinct.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Replace Values", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.replace.inplace, false, "New");
+                ui.radio_value(&mut self.replace.inplace, true, "In Place");
+            });
+            ComboBox::new("replace_column", "")
+                .selected_text(&self.replace.column)
+                .show_ui(ui, |ui| {
+                    for col in &self.columns {
+                        ui.selectable_value(&mut self.replace.column, col.to_owned(), col);
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.replace.regex, false, "Values");
+                ui.radio_value(&mut self.replace.regex, true, "Regex");
+                let hint = match self.replace.regex {
+                    true => "^\\s*$",
+                    false => "N/A, na, -",
+                };
+                ui.add(
+                    TextEdit::singleline(&mut self.replace.find)
+                        .hint_text(hint)
+                        .desired_width(150.0),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("With:");
+                ui.add_enabled(
+                    !self.replace.null,
+                    TextEdit::singleline(&mut self.replace.value).desired_width(100.0),
+                );
+                ui.checkbox(&mut self.replace.null, "Null");
+            });
+            self.replace.update_preview(&self.data);
+            match &self.replace.preview {
+                Some((_, _, _, Ok(count))) => {
+                    ui.label(format!("{} loaded cells will change", count));
+                }
+                Some((_, _, _, Err(e))) => {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+                None => {}
+            }
+            if ui.button("Replace").clicked() {
+                let result = self.replace.apply(self.lazy_frame());
+                let inplace = self.replace.inplace;
+                self.replace.error = self.apply_result("replaced", result, inplace);
+            }
+            if let Some(error) = &self.replace.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("String Ops", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.string_op.inplace, false, "New");
+                ui.radio_value(&mut self.string_op.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("string_column", "")
+                    .selected_text(&self.string_op.column)
+                    .show_ui(ui, |ui| {
+                        for (col, dtype) in self.columns.iter().zip(self.data.dtypes()) {
+                            if dtype == DataType::String {
+                                ui.selectable_value(
+                                    &mut self.string_op.column,
+                                    col.to_owned(),
+                                    col,
+                                );
+                            }
+                        }
+                    });
+                ComboBox::new("string_op", "")
+                    .selected_text(self.string_op.op.label())
+                    .show_ui(ui, |ui| {
+                        for op in StringOp::ALL {
+                            ui.selectable_value(&mut self.string_op.op, op, op.label());
+                        }
+                    });
+            });
+            match self.string_op.op {
+                StringOp::PadStart | StringOp::PadEnd => {
+                    ui.horizontal(|ui| {
+                        ui.label("Width:");
+                        ui.add(DragValue::new(&mut self.string_op.width).range(0..=1000));
+                        ui.label("Fill:");
+                        ui.add(
+                            TextEdit::singleline(&mut self.string_op.fill)
+                                .char_limit(1)
+                                .desired_width(20.0),
+                        );
+                    });
+                }
+                StringOp::Slice => {
+                    ui.horizontal(|ui| {
+                        ui.label("Start:");
+                        ui.add(DragValue::new(&mut self.string_op.offset));
+                        let mut limited = self.string_op.length.is_some();
+                        if ui.checkbox(&mut limited, "Length:").changed() {
+                            self.string_op.length = limited.then_some(1);
+                        }
+                        if let Some(length) = &mut self.string_op.length {
+                            ui.add(DragValue::new(length));
+                        }
+                    });
+                }
+                _ => {}
+            }
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.string_op.new_column, false, "Replace column");
+                ui.radio_value(&mut self.string_op.new_column, true, "New column:");
+                ui.add_enabled(
+                    self.string_op.new_column,
+                    TextEdit::singleline(&mut self.string_op.name).desired_width(100.0),
+                );
+            });
+            if ui.button("Apply").clicked() {
+                let result = self.string_op.apply(self.lazy_frame());
+                let inplace = self.string_op.inplace;
+                self.string_op.error = self.apply_result("strings", result, inplace);
+            }
+            if let Some(error) = &self.string_op.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Regex Extract", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.extract.inplace, false, "New");
+                ui.radio_value(&mut self.extract.inplace, true, "In Place");
+            });
+            ComboBox::new("extract_column", "")
+                .selected_text(&self.extract.column)
+                .show_ui(ui, |ui| {
+                    for (col, dtype) in self.columns.iter().zip(self.data.dtypes()) {
+                        if dtype == DataType::String {
+                            ui.selectable_value(&mut self.extract.column, col.to_owned(), col);
+                        }
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.label("Regex:");
+                ui.add(
+                    TextEdit::singleline(&mut self.extract.pattern)
+                        .hint_text("(\\d+)-(\\d+)")
+                        .desired_width(150.0),
+                );
+                ui.label("Group:");
+                ui.add(DragValue::new(&mut self.extract.group).range(0..=99));
+            });
+            ui.horizontal(|ui| {
+                ui.label("New column:");
+                ui.add(TextEdit::singleline(&mut self.extract.name).desired_width(100.0));
+            });
+            self.extract.update_preview(&self.data);
+            match self.extract.preview.as_ref().map(|p| &p.rows) {
+                Some(Ok(rows)) => {
+                    Grid::new("extract_preview").striped(true).show(ui, |ui| {
+                        for (text, extracted) in rows {
+                            ui.label(text);
+                            ui.label("→");
+                            ui.label(extracted);
+                            ui.end_row();
+                        }
+                    });
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+                None => {}
+            }
+            if ui.button("Extract").clicked() {
+                let result = self.extract.apply(self.lazy_frame());
+                let inplace = self.extract.inplace;
+                self.extract.error = self.apply_result("extracted", result, inplace);
+            }
+            if let Some(error) = &self.extract.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Concat Columns", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.concat_columns.inplace, false, "New");
+                ui.radio_value(&mut self.concat_columns.inplace, true, "In Place");
+            });
+            column_checkboxes(
+                "concat_columns",
+                &self.columns,
+                &mut self.concat_columns.columns,
+                ui,
+            );
+            if !self.concat_columns.columns.is_empty() {
+                ui.label(format!("Order: {}", self.concat_columns.columns.join(", ")));
+            }
+            ui.horizontal(|ui| {
+                ui.label("Separator:");
+                ui.add(
+                    TextEdit::singleline(&mut self.concat_columns.separator).desired_width(40.0),
+                );
+                ui.checkbox(&mut self.concat_columns.ignore_nulls, "Skip nulls");
+            });
+            ui.horizontal(|ui| {
+                ui.label("New column:");
+                ui.add(TextEdit::singleline(&mut self.concat_columns.name).desired_width(100.0));
+            });
+            if ui.button("Concatenate").clicked() {
+                let result = self.concat_columns.apply(self.lazy_frame());
+                let inplace = self.concat_columns.inplace;
+                self.concat_columns.error = self.apply_result("combined", result, inplace);
+            }
+            if let Some(error) = &self.concat_columns.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Split Column", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.split.inplace, false, "New");
+                ui.radio_value(&mut self.split.inplace, true, "In Place");
+            });
+            ComboBox::new("split_column", "")
+                .selected_text(&self.split.column)
+                .show_ui(ui, |ui| {
+                    for (col, dtype) in self.columns.iter().zip(self.data.dtypes()) {
+                        if dtype == DataType::String {
+                            ui.selectable_value(&mut self.split.column, col.to_owned(), col);
+                        }
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.label("On:");
+                ui.add(TextEdit::singleline(&mut self.split.delimiter).desired_width(80.0));
+                ui.checkbox(&mut self.split.regex, "Regex").on_hover_text(
+                    "Texts with the control character U+001F can't be split on a regex",
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.split.into_list, false, "Columns:");
+                ui.add_enabled(
+                    !self.split.into_list,
+                    DragValue::new(&mut self.split.parts).range(1..=100),
+                );
+                ui.radio_value(&mut self.split.into_list, true, "List column");
+            });
+            if !self.split.into_list {
+                ui.checkbox(&mut self.split.strip, "Strip whitespace");
+            }
+            ui.horizontal(|ui| {
+                let label = match self.split.into_list {
+                    true => "Name:",
+                    false => "Prefix:",
+                };
+                ui.label(label);
+                ui.add(
+                    TextEdit::singleline(&mut self.split.name)
+                        .hint_text(&self.split.column)
+                        .desired_width(100.0),
+                );
+            });
+            if ui.button("Split").clicked() {
+                let result = self.split.apply(self.lazy_frame());
+                let inplace = self.split.inplace;
+                self.split.error = self.apply_result("split", result, inplace);
+            }
+            if let Some(error) = &self.split.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Anonymize", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.anonymize.inplace, false, "New");
+                ui.radio_value(&mut self.anonymize.inplace, true, "In Place");
+            });
+            ComboBox::new("anonymize_column", "")
+                .selected_text(&self.anonymize.column)
+                .show_ui(ui, |ui| {
+                    for col in &self.columns {
+                        ui.selectable_value(&mut self.anonymize.column, col.to_owned(), col);
+                    }
+                });
+            ui.horizontal(|ui| {
+                for mode in AnonymizeMode::ALL {
+                    ui.radio_value(&mut self.anonymize.mode, mode, mode.label());
+                }
+            });
+            match self.anonymize.mode {
+                AnonymizeMode::Hash => {
+                    ui.horizontal(|ui| {
+                        ui.label("Salt:");
+                        ui.add(
+                            TextEdit::singleline(&mut self.anonymize.salt)
+                                .password(true)
+                                .hint_text("makes the hashes unguessable")
+                                .desired_width(150.0),
+                        );
+                    });
+                }
+                AnonymizeMode::Mask => {
+                    ui.horizontal(|ui| {
+                        ui.label("Keep the last");
+                        ui.add(DragValue::new(&mut self.anonymize.keep).range(0..=100));
+                        ui.label("characters");
+                    });
+                }
+                AnonymizeMode::Token => {}
+            }
+            if ui.button("Anonymize").clicked() {
+                let seed = ui.input(|i| i.time).to_bits();
+                let result = self.anonymize.apply(self.lazy_frame(), seed);
+                let inplace = self.anonymize.inplace;
+                self.anonymize.error = self.apply_result("anonymized", result, inplace);
+            }
+            if let Some(error) = &self.anonymize.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Parse Dates", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.parse_dates.inplace, false, "New");
+                ui.radio_value(&mut self.parse_dates.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("parse_dates_column", "to")
+                    .selected_text(&self.parse_dates.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.parse_dates.column, col.to_owned(), col);
+                        }
+                    });
+                ui.radio_value(&mut self.parse_dates.datetime, false, "Date");
+                ui.radio_value(&mut self.parse_dates.datetime, true, "Datetime");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Format:");
+                ui.add(
+                    TextEdit::singleline(&mut self.parse_dates.format)
+                        .hint_text("detect, or e.g. %d/%m/%Y")
+                        .desired_width(150.0),
+                );
+            });
+            self.parse_dates.update_preview(&self.data);
+            match self.parse_dates.preview.as_ref().map(|p| &p.failures) {
+                Some(Ok((0, _))) => {
+                    ui.label("All the loaded values parse");
+                }
+                Some(Ok((count, texts))) => {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!("{} loaded values won't parse and will be null:", count),
+                    );
+                    for text in texts {
+                        ui.label(egui::RichText::new(text).monospace());
+                    }
+                }
+                Some(Err(e)) => {
+                    ui.colored_label(ui.visuals().error_fg_color, e);
+                }
+                None => {}
+            }
+            if ui.button("Parse").clicked() {
+                let result = self.parse_dates.apply(self.lazy_frame());
+                let inplace = self.parse_dates.inplace;
+                self.parse_dates.error = self.apply_result("parsed", result, inplace);
+            }
+            if let Some(error) = &self.parse_dates.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Date Parts", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.date_parts.inplace, false, "New");
+                ui.radio_value(&mut self.date_parts.inplace, true, "In Place");
+            });
+            ComboBox::new("date_parts_column", "")
+                .selected_text(&self.date_parts.column)
+                .show_ui(ui, |ui| {
+                    for col in &self.columns {
+                        ui.selectable_value(&mut self.date_parts.column, col.to_owned(), col);
+                    }
+                });
+            ui.horizontal_wrapped(|ui| {
+                for part in DatePart::ALL {
+                    let mut checked = self.date_parts.parts.contains(&part);
+                    if ui.checkbox(&mut checked, part.label()).changed() {
+                        match checked {
+                            true => self.date_parts.parts.push(part),
+                            false => self.date_parts.parts.retain(|p| p != &part),
+                        }
+                    }
+                }
+            });
+            if ui.button("Extract").clicked() {
+                let result = self.date_parts.apply(self.lazy_frame());
+                let inplace = self.date_parts.inplace;
+                self.date_parts.error = self.apply_result("date parts", result, inplace);
+            }
+            if let Some(error) = &self.date_parts.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Time Zone", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.time_zone.inplace, false, "New");
+                ui.radio_value(&mut self.time_zone.inplace, true, "In Place");
+            });
+            ComboBox::new("time_zone_column", "")
+                .selected_text(&self.time_zone.column)
+                .show_ui(ui, |ui| {
+                    for col in &self.columns {
+                        ui.selectable_value(&mut self.time_zone.column, col.to_owned(), col);
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.time_zone.convert, true, "Convert to")
+                    .on_hover_text("Times without a zone are read as UTC");
+                ui.radio_value(&mut self.time_zone.convert, false, "Set to")
+                    .on_hover_text("Keeps the times, an empty zone removes it");
+                ui.add(
+                    TextEdit::singleline(&mut self.time_zone.time_zone)
+                        .hint_text("e.g. America/Buenos_Aires")
+                        .desired_width(180.0),
+                );
+            });
+            if ui.button("Apply").clicked() {
+                let result = self.time_zone.apply(self.lazy_frame());
+                let inplace = self.time_zone.inplace;
+                self.time_zone.error = self.apply_result("time zone", result, inplace);
+            }
+            if let Some(error) = &self.time_zone.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Upsample", |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.upsample.inplace, false, "New");
+                ui.radio_value(&mut self.upsample.inplace, true, "In Place");
+            });
+            ui.horizontal(|ui| {
+                ComboBox::new("upsample_column", "every")
+                    .selected_text(&self.upsample.column)
+                    .show_ui(ui, |ui| {
+                        for col in &self.columns {
+                            ui.selectable_value(&mut self.upsample.column, col.to_owned(), col);
+                        }
+                    });
+                ui.add(TextEdit::singleline(&mut self.upsample.every).desired_width(60.0));
+            });
+            ui.label("Within groups of:");
+            column_checkboxes("upsample_by", &self.columns, &mut self.upsample.by, ui);
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.upsample.interpolate, false, "Forward fill");
+                ui.radio_value(&mut self.upsample.interpolate, true, "Interpolate")
+                    .on_hover_text("Linear interpolation of the numeric columns");
+            });
+            if ui.button("Upsample").clicked() {
+                let result = self.upsample.apply(self.lazy_frame());
+                let inplace = self.upsample.inplace;
+                self.upsample.error = self.apply_result("upsampled", result, inplace);
+            }
+            if let Some(error) = &self.upsample.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.collapsing("Export", |ui| {
+            let mut format = None;
+            ui.horizontal(|ui| {
+                ui.label("Separator:");
+                ui.add(TextEdit::singleline(&mut self.export.separator).desired_width(30.0));
+                ui.checkbox(&mut self.export.has_header, "Header");
+                if ui.button("CSV").clicked() {
+                    format = Some(ExportFormat::Csv);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Compression:");
+                ui.radio_value(
+                    &mut self.export.compression,
+                    ExportCompression::Snappy,
+                    "Snappy",
+                );
+                ui.radio_value(
+                    &mut self.export.compression,
+                    ExportCompression::Zstd,
+                    "Zstd",
+                );
+                ui.radio_value(
+                    &mut self.export.compression,
+                    ExportCompression::Uncompressed,
+                    "Uncompressed",
+                );
+                if ui.button("Parquet").clicked() {
+                    format = Some(ExportFormat::Parquet);
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button("JSON").clicked() {
+                    format = Some(ExportFormat::Json);
+                }
+                if ui.button("NDJSON").clicked() {
+                    format = Some(ExportFormat::NdJson);
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.export.excel_summary, "Summary sheet");
+                if ui.button("Excel").clicked() {
+                    format = Some(ExportFormat::Excel);
+                }
+            });
+            if let Some(format) = format {
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(path) = export::save_path(&self.title, &format) {
+                    self.export.error = self.export_file(&path, &format).err();
+                }
+                // the browser has no file system, the export is offered as a download instead.
+                #[cfg(target_arch = "wasm32")]
+                match self
+                    .lazy_frame()
+                    .collect()
+                    .and_then(|mut df| self.export.to_bytes(&mut df, &format))
+                {
+                    Ok(bytes) => export::download(&self.title, &format, bytes),
+                    Err(e) => self.export.error = Some(e.to_string()),
+                }
+            }
+            if let Some(error) = &self.export.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+    }
+}
+
+/// Quick views of a few rows of a container.
+enum Peek {
+    Head,
+    Tail,
+    Sample,
+}
+
+/// Rows of a container copied to the clipboard.
+enum CopyRows {
+    All,
+    Visible,
+    Selected,
 }
@@ -0,0 +1,332 @@
+use crate::utils::{interpolate_linear, is_duration};
+use polars::prelude::*;
+
+/// Failed texts shown in the preview of a date parsing.
+const PREVIEW_FAILURES: usize = 5;
+
+/// Parses a text column to Date or Datetime values, with a strptime format like `%d/%m/%Y` or
+/// the format detected from the values.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameParseDates {
+    pub column: String,
+    /// Parse to Datetime instead of Date.
+    pub datetime: bool,
+    /// Detected if empty.
+    pub format: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+    pub preview: Option<ParsePreview>,
+}
+
+/// Texts of the loaded rows that won't parse, or the parsing error, with the inputs they were
+/// found for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsePreview {
+    column: String,
+    datetime: bool,
+    format: String,
+    /// The number of failures and the first failed texts.
+    pub failures: Result<(usize, Vec<String>), String>,
+}
+
+impl DataFrameParseDates {
+    fn expr(&self) -> Expr {
+        let format = self.format.trim();
+        let options = StrptimeOptions {
+            format: (!format.is_empty()).then(|| format.to_string()),
+            // texts that don't parse become nulls, the preview lists them beforehand.
+            strict: false,
+            ..Default::default()
+        };
+        match self.datetime {
+            true => col(&self.column)
+                .str()
+                .to_datetime(None, None, options, lit("raise")),
+            false => col(&self.column).str().to_date(options),
+        }
+    }
+
+    fn check_column(&self, schema: &Schema) -> PolarsResult<()> {
+        let dtype = schema.try_get(&self.column)?;
+        if dtype != &DataType::String {
+            polars_bail!(ComputeError: "'{}' is {}, only text can be parsed", self.column, dtype);
+        }
+        Ok(())
+    }
+
+    /// Looks for the texts that won't parse in the loaded rows whenever the inputs change.
+    pub fn update_preview(&mut self, df: &DataFrame) {
+        if self.column.is_empty() {
+            self.preview = None;
+            return;
+        }
+        if let Some(preview) = &self.preview {
+            if (&preview.column, preview.datetime, &preview.format)
+                == (&self.column, self.datetime, &self.format)
+            {
+                return;
+            }
+        }
+        let failures = self
+            .check_column(&df.schema())
+            .and_then(|_| {
+                let failed = df
+                    .clone()
+                    .lazy()
+                    .filter(col(&self.column).is_not_null().and(self.expr().is_null()))
+                    .select([col(&self.column)])
+                    .collect()?;
+                let texts = failed
+                    .column(&self.column)?
+                    .str()?
+                    .into_no_null_iter()
+                    .take(PREVIEW_FAILURES)
+                    .map(String::from)
+                    .collect();
+                Ok((failed.height(), texts))
+            })
+            .map_err(|e| e.to_string());
+        self.preview = Some(ParsePreview {
+            column: self.column.clone(),
+            datetime: self.datetime,
+            format: self.format.clone(),
+            failures,
+        });
+    }
+
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        let schema = df.schema()?;
+        self.check_column(&schema)?;
+        df.with_column(self.expr()).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DatePart {
+    Year,
+    Quarter,
+    Month,
+    Week,
+    Day,
+    Weekday,
+    Hour,
+    Minute,
+}
+
+impl DatePart {
+    pub const ALL: [DatePart; 8] = [
+        DatePart::Year,
+        DatePart::Quarter,
+        DatePart::Month,
+        DatePart::Week,
+        DatePart::Day,
+        DatePart::Weekday,
+        DatePart::Hour,
+        DatePart::Minute,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DatePart::Year => "year",
+            DatePart::Quarter => "quarter",
+            DatePart::Month => "month",
+            DatePart::Week => "week",
+            DatePart::Day => "day",
+            DatePart::Weekday => "weekday",
+            DatePart::Hour => "hour",
+            DatePart::Minute => "minute",
+        }
+    }
+
+    /// Whether the part is a time of day, which Date values don't have.
+    fn is_time(&self) -> bool {
+        matches!(self, DatePart::Hour | DatePart::Minute)
+    }
+
+    fn apply(&self, expr: Expr) -> Expr {
+        match self {
+            DatePart::Year => expr.dt().year(),
+            DatePart::Quarter => expr.dt().quarter(),
+            DatePart::Month => expr.dt().month(),
+            DatePart::Week => expr.dt().week(),
+            DatePart::Day => expr.dt().day(),
+            // 1 for Monday to 7 for Sunday.
+            DatePart::Weekday => expr.dt().weekday(),
+            DatePart::Hour => expr.dt().hour(),
+            DatePart::Minute => expr.dt().minute(),
+        }
+    }
+}
+
+/// A new column `{column}_{part}` for each picked part of a Date or Datetime column, e.g. the
+/// month to aggregate by.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameDateParts {
+    pub column: String,
+    pub parts: Vec<DatePart>,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameDateParts {
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to extract from");
+        }
+        if self.parts.is_empty() {
+            polars_bail!(ComputeError: "pick the parts to extract");
+        }
+        let schema = df.schema()?;
+        let dtype = schema.try_get(&self.column)?;
+        match dtype {
+            DataType::Datetime(_, _) => {}
+            DataType::Date => {
+                if let Some(part) = self.parts.iter().find(|p| p.is_time()) {
+                    polars_bail!(ComputeError: "'{}' is a Date, it has no {}", self.column, part.label());
+                }
+            }
+            _ => {
+                polars_bail!(ComputeError: "'{}' is {}, not a Date or Datetime", self.column, dtype)
+            }
+        }
+        // in the order of the parts rather than the order they were picked in.
+        let exprs: Vec<Expr> = DatePart::ALL
+            .iter()
+            .filter(|p| self.parts.contains(p))
+            .map(|p| {
+                p.apply(col(&self.column))
+                    .alias(&format!("{}_{}", self.column, p.label()))
+            })
+            .collect();
+        df.with_columns(exprs).collect()
+    }
+}
+
+/// Sets the time zone of a Datetime column, keeping the wall clock times, or converts its
+/// values to the times of another zone.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameTimeZone {
+    pub column: String,
+    /// Convert the values instead of setting the zone.
+    pub convert: bool,
+    /// An IANA name like "America/Buenos_Aires". Setting an empty zone removes it.
+    pub time_zone: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameTimeZone {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            convert: true,
+            time_zone: String::default(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameTimeZone {
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to set the time zone of");
+        }
+        let schema = df.schema()?;
+        let current = match schema.try_get(&self.column)? {
+            DataType::Datetime(_, time_zone) => time_zone.clone(),
+            dtype => polars_bail!(ComputeError: "'{}' is {}, not a Datetime", self.column, dtype),
+        };
+        let time_zone = self.time_zone.trim();
+        let time_zone = (!time_zone.is_empty()).then(|| time_zone.to_string());
+        let set = |expr: Expr, time_zone| {
+            expr.dt()
+                .replace_time_zone(time_zone, lit("raise"), NonExistent::Raise)
+        };
+        let expr = match (self.convert, time_zone) {
+            (false, time_zone) => set(col(&self.column), time_zone),
+            (true, None) => polars_bail!(ComputeError: "type the time zone to convert to"),
+            // times without a zone are read as UTC.
+            (true, Some(time_zone)) => match current {
+                Some(_) => col(&self.column).dt().convert_time_zone(time_zone),
+                None => set(col(&self.column), Some(String::from("UTC")))
+                    .dt()
+                    .convert_time_zone(time_zone),
+            },
+        };
+        df.with_column(expr).collect()
+    }
+}
+
+/// Expands a frame to a row every `every` between the first and last times of `column`, per
+/// group of the `by` columns, filling the values of the new rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameUpsample {
+    pub column: String,
+    pub every: String,
+    pub by: Vec<String>,
+    /// Interpolate the numeric columns linearly instead of carrying the last values forward.
+    /// The other columns are always carried forward.
+    pub interpolate: bool,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameUpsample {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            every: String::from("1h"),
+            by: Vec::new(),
+            interpolate: false,
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameUpsample {
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the time column to upsample");
+        }
+        let schema = df.schema()?;
+        let dtype = schema.try_get(&self.column)?;
+        if !matches!(dtype, DataType::Date | DataType::Datetime(_, _)) {
+            polars_bail!(ComputeError: "'{}' is {}, not a Date or Datetime", self.column, dtype);
+        }
+        let every = self.every.trim();
+        if !is_duration(every) {
+            polars_bail!(ComputeError: "'{}' is not an interval like 1h or 15m", every);
+        }
+        let sorted = df.sort([&self.column], Default::default()).collect()?;
+        let upsampled =
+            sorted.upsample_stable(self.by.clone(), &self.column, Duration::parse(every))?;
+        // rows of the original frame that aren't on the interval are dropped by upsampling, so
+        // the rows are evenly spaced and interpolating by position is interpolating by time.
+        let fills: Vec<Expr> = schema
+            .iter()
+            .filter(|(name, _)| {
+                name.as_str() != self.column && !self.by.contains(&name.to_string())
+            })
+            .map(|(name, dtype)| {
+                let value = col(name);
+                let filled = match self.interpolate && dtype.is_numeric() {
+                    true => interpolate_linear(value, dtype),
+                    false => value.forward_fill(None),
+                };
+                match self.by.is_empty() {
+                    true => filled,
+                    false => filled.over(self.by.iter().map(|c| col(c)).collect::<Vec<_>>()),
+                }
+            })
+            .collect();
+        // the new rows have no group either, each group's rows follow its first, original row.
+        let groups: Vec<Expr> = self.by.iter().map(|c| col(c).forward_fill(None)).collect();
+        upsampled
+            .lazy()
+            .with_columns(groups)
+            .with_columns(fills)
+            .collect()
+    }
+}
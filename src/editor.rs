@@ -0,0 +1,140 @@
+use crate::reader::{read_csv, ColumnType, CsvImportOptions, ImportSource};
+use egui::{ComboBox, Grid, TextEdit, Window};
+use polars::prelude::*;
+
+/// Spreadsheet-like window to type in a small DataFrame, e.g. a lookup table to join against.
+#[derive(Clone, Debug)]
+pub struct DataFrameEditor {
+    pub title: String,
+    pub columns: Vec<(String, ColumnType)>,
+    pub rows: Vec<Vec<String>>,
+    pub error: Option<String>,
+    pub is_open: bool,
+    pub create: bool,
+}
+
+impl Default for DataFrameEditor {
+    fn default() -> Self {
+        Self {
+            title: String::from("new_frame"),
+            columns: vec![(String::from("column_1"), ColumnType::String)],
+            rows: vec![vec![String::new()]],
+            error: None,
+            is_open: true,
+            create: false,
+        }
+    }
+}
+
+impl DataFrameEditor {
+    /// Builds the DataFrame. The typed text goes through the CSV reader so values are parsed
+    /// to the column types the same way as imported files. Empty cells are null.
+    pub fn to_dataframe(&self) -> PolarsResult<DataFrame> {
+        let series: Vec<Series> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| {
+                let values: Vec<Option<&str>> = self
+                    .rows
+                    .iter()
+                    .map(|row| Some(row[i].trim()).filter(|v| !v.is_empty()))
+                    .collect();
+                Series::new(name, values)
+            })
+            .collect();
+        let mut buffer = Vec::new();
+        CsvWriter::new(&mut buffer).finish(&mut DataFrame::new(series)?)?;
+        let options = CsvImportOptions {
+            dtypes: self.columns.clone(),
+            ..Default::default()
+        };
+        read_csv(&ImportSource::Bytes(buffer), &options, None)
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut is_open = std::mem::take(&mut self.is_open);
+        Window::new("New DataFrame")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Title:");
+                    ui.text_edit_singleline(&mut self.title);
+                });
+                ui.add_space(10.0);
+                egui::ScrollArea::both().max_height(400.0).show(ui, |ui| {
+                    self.show_grid(ui);
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Add column").clicked() {
+                        let name = format!("column_{}", self.columns.len() + 1);
+                        self.columns.push((name, ColumnType::String));
+                        for row in self.rows.iter_mut() {
+                            row.push(String::new());
+                        }
+                    }
+                    if ui.button("Add row").clicked() {
+                        self.rows.push(vec![String::new(); self.columns.len()]);
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.add_space(10.0);
+                if ui
+                    .add_enabled(!self.columns.is_empty(), egui::Button::new("Create"))
+                    .clicked()
+                {
+                    self.create = true;
+                }
+            });
+        self.is_open = is_open;
+    }
+
+    /// Column names and types on top, one text field per cell below. Columns and rows can
+    /// be removed with the ✖ buttons.
+    fn show_grid(&mut self, ui: &mut egui::Ui) {
+        let mut remove_column = None;
+        let mut remove_row = None;
+        Grid::new("editor_grid").striped(true).show(ui, |ui| {
+            ui.label("");
+            for (i, (name, column_type)) in self.columns.iter_mut().enumerate() {
+                ui.vertical(|ui| {
+                    ui.horizontal(|ui| {
+                        ui.add(TextEdit::singleline(name).desired_width(90.0));
+                        if ui.small_button("✖").clicked() {
+                            remove_column = Some(i);
+                        }
+                    });
+                    ComboBox::from_id_source(("editor_column_type", i))
+                        .selected_text(format!("{:?}", column_type))
+                        .show_ui(ui, |ui| {
+                            for option in ColumnType::ALL {
+                                ui.selectable_value(column_type, option, format!("{:?}", option));
+                            }
+                        });
+                });
+            }
+            ui.end_row();
+            for (r, row) in self.rows.iter_mut().enumerate() {
+                if ui.small_button("✖").clicked() {
+                    remove_row = Some(r);
+                }
+                for cell in row.iter_mut() {
+                    ui.add(TextEdit::singleline(cell).desired_width(110.0));
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(i) = remove_column {
+            self.columns.remove(i);
+            for row in self.rows.iter_mut() {
+                row.remove(i);
+            }
+        }
+        if let Some(r) = remove_row {
+            self.rows.remove(r);
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::filter::split_values;
+use crate::utils::column_name;
+use polars::prelude::*;
+
+/// A categorical column with the bin of each value of a numeric column, either between
+/// breakpoints typed in or in one of `bins` quantiles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameBin {
+    pub column: String,
+    /// Bin by quantiles instead of breakpoints.
+    pub quantiles: bool,
+    /// Comma separated breakpoints, e.g. "0, 18, 65".
+    pub breaks: String,
+    pub bins: usize,
+    /// Comma separated labels, one per bin. The intervals are used if empty.
+    pub labels: String,
+    /// Bins include their lower bound instead of their upper bound.
+    pub left_closed: bool,
+    /// Name of the new column, `{column}_bin` if empty.
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameBin {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            quantiles: false,
+            breaks: String::default(),
+            bins: 4,
+            labels: String::default(),
+            left_closed: false,
+            name: String::default(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameBin {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to bin");
+        }
+        let labels: Vec<String> = split_values(&self.labels)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let labels = (!labels.is_empty()).then_some(labels);
+        let value = col(&self.column);
+        let expr = match self.quantiles {
+            true => {
+                if self.bins < 2 {
+                    polars_bail!(ComputeError: "quantile binning needs at least 2 bins");
+                }
+                value.qcut_uniform(self.bins, labels, self.left_closed, true, false)
+            }
+            false => {
+                let mut breaks = Vec::new();
+                for text in split_values(&self.breaks) {
+                    match text.parse::<f64>() {
+                        Ok(b) => breaks.push(b),
+                        Err(_) => {
+                            polars_bail!(ComputeError: "the breakpoint '{}' is not a number", text)
+                        }
+                    }
+                }
+                if breaks.is_empty() {
+                    polars_bail!(ComputeError: "type the breakpoints, e.g. 0, 18, 65");
+                }
+                value.cut(breaks, labels, self.left_closed, false)
+            }
+        };
+        let name = column_name(&self.name, &self.column, "bin");
+        df.with_column(expr.alias(&name)).collect()
+    }
+}
+
+/// Replaces columns by indicator columns, one per distinct value, named `{column}_{value}`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameOneHot {
+    pub columns: Vec<String>,
+    /// Added before the names of the indicator columns.
+    pub prefix: String,
+    /// Leave out the indicator of the first value, implied when all the others are 0.
+    pub drop_first: bool,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameOneHot {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.columns.is_empty() {
+            polars_bail!(ComputeError: "select the columns to encode");
+        }
+        let df = df.collect()?;
+        let columns = self.columns.iter().map(|c| c.as_str()).collect();
+        let mut encoded = df.columns_to_dummies(columns, Some("_"), self.drop_first)?;
+        if !self.prefix.is_empty() {
+            let names: Vec<String> = encoded
+                .get_column_names()
+                .iter()
+                .map(|name| match df.get_column_index(name) {
+                    Some(_) => name.to_string(),
+                    None => format!("{}{}", self.prefix, name),
+                })
+                .collect();
+            encoded.set_column_names(&names)?;
+        }
+        Ok(encoded)
+    }
+}
+
+/// Replaces the values of a text column by Categorical values, or by integer codes numbering
+/// the distinct values in order of appearance.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameLabelEncode {
+    pub column: String,
+    /// Integer codes instead of a Categorical column.
+    pub codes: bool,
+    pub inplace: bool,
+    /// The value of each code, from the last encoding.
+    pub mapping: Option<DataFrame>,
+    pub show_mapping: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameLabelEncode {
+    /// The encoded frame, and the mapping from values to codes when encoding to codes.
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<(DataFrame, Option<DataFrame>)> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to encode");
+        }
+        let column = self.column.as_str();
+        if !self.codes {
+            let categorical = DataType::Categorical(None, Default::default());
+            let df = df.with_column(col(column).cast(categorical)).collect()?;
+            return Ok((df, None));
+        }
+        let mapping = df
+            .clone()
+            .select([col(column)])
+            .drop_nulls(None)
+            .unique_stable(None, UniqueKeepStrategy::First)
+            .with_row_index("code", None)
+            .collect()?;
+        let code = format!("{}_code", column);
+        // left joins keep the order of the rows, nulls stay null.
+        let encoded = df
+            .join(
+                mapping.clone().lazy().rename(["code"], [code.as_str()]),
+                [col(column)],
+                [col(column)],
+                JoinArgs::new(JoinType::Left),
+            )
+            .with_column(col(&code).alias(column))
+            .drop([code.as_str()])
+            .collect()?;
+        let mapping = mapping.select([column, "code"])?;
+        Ok((encoded, Some(mapping)))
+    }
+}
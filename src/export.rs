@@ -0,0 +1,216 @@
+use crate::reader::parse_char;
+use crate::utils::describe_dataframe;
+use polars::prelude::*;
+use rust_xlsxwriter::{Format, Workbook, Worksheet, XlsxError};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportFormat {
+    Csv,
+    Parquet,
+    Json,
+    NdJson,
+    Excel,
+}
+
+impl ExportFormat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Parquet => "Parquet",
+            ExportFormat::Json => "JSON",
+            ExportFormat::NdJson => "NDJSON",
+            ExportFormat::Excel => "Excel",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Parquet => "parquet",
+            ExportFormat::Json => "json",
+            ExportFormat::NdJson => "ndjson",
+            ExportFormat::Excel => "xlsx",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExportCompression {
+    Snappy,
+    Zstd,
+    Uncompressed,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameExport {
+    pub separator: String,
+    pub has_header: bool,
+    pub compression: ExportCompression,
+    pub excel_summary: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameExport {
+    fn default() -> Self {
+        Self {
+            separator: String::from(","),
+            has_header: true,
+            compression: ExportCompression::Snappy,
+            excel_summary: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameExport {
+    /// Serializes the frame in the given format with the chosen options.
+    pub fn to_bytes(&self, df: &mut DataFrame, format: &ExportFormat) -> PolarsResult<Vec<u8>> {
+        let mut buffer = Vec::new();
+        match format {
+            ExportFormat::Csv => CsvWriter::new(&mut buffer)
+                .include_header(self.has_header)
+                .with_separator(parse_char(&self.separator).unwrap_or(b','))
+                .finish(df)?,
+            ExportFormat::Parquet => {
+                let compression = match self.compression {
+                    ExportCompression::Snappy => ParquetCompression::Snappy,
+                    ExportCompression::Zstd => ParquetCompression::Zstd(None),
+                    ExportCompression::Uncompressed => ParquetCompression::Uncompressed,
+                };
+                ParquetWriter::new(&mut buffer)
+                    .with_compression(compression)
+                    .finish(df)?;
+            }
+            ExportFormat::Json => JsonWriter::new(&mut buffer)
+                .with_json_format(JsonFormat::Json)
+                .finish(df)?,
+            ExportFormat::NdJson => JsonWriter::new(&mut buffer)
+                .with_json_format(JsonFormat::JsonLines)
+                .finish(df)?,
+            ExportFormat::Excel => buffer = self.to_xlsx(df)?,
+        }
+        Ok(buffer)
+    }
+
+    /// Writes the frame to a "Data" sheet, plus its `describe()` output to a "Summary" sheet
+    /// if enabled.
+    fn to_xlsx(&self, df: &DataFrame) -> PolarsResult<Vec<u8>> {
+        let xlsx_error = |e: XlsxError| polars_err!(ComputeError: "{}", e);
+        let mut workbook = Workbook::new();
+        write_sheet(
+            workbook
+                .add_worksheet()
+                .set_name("Data")
+                .map_err(xlsx_error)?,
+            df,
+        )?;
+        if self.excel_summary {
+            let summary = describe_dataframe(df)?;
+            write_sheet(
+                workbook
+                    .add_worksheet()
+                    .set_name("Summary")
+                    .map_err(xlsx_error)?,
+                &summary,
+            )?;
+        }
+        workbook.save_to_buffer().map_err(xlsx_error)
+    }
+}
+
+/// Writes a bold header row followed by the values. Numbers and booleans keep their type,
+/// everything else is written as text.
+fn write_sheet(sheet: &mut Worksheet, df: &DataFrame) -> PolarsResult<()> {
+    let xlsx_error = |e: XlsxError| polars_err!(ComputeError: "{}", e);
+    let bold = Format::new().set_bold();
+    for (c, series) in df.get_columns().iter().enumerate() {
+        let c = c as u16;
+        sheet
+            .write_string_with_format(0, c, series.name(), &bold)
+            .map_err(xlsx_error)?;
+        if series.dtype().is_numeric() {
+            let values = series.cast(&DataType::Float64)?;
+            for (r, value) in values.f64()?.into_iter().enumerate() {
+                if let Some(value) = value {
+                    sheet
+                        .write_number(r as u32 + 1, c, value)
+                        .map_err(xlsx_error)?;
+                }
+            }
+        } else if series.dtype() == &DataType::Boolean {
+            for (r, value) in series.bool()?.into_iter().enumerate() {
+                if let Some(value) = value {
+                    sheet
+                        .write_boolean(r as u32 + 1, c, value)
+                        .map_err(xlsx_error)?;
+                }
+            }
+        } else {
+            let values = series.cast(&DataType::String)?;
+            for (r, value) in values.str()?.into_iter().enumerate() {
+                if let Some(value) = value {
+                    sheet
+                        .write_string(r as u32 + 1, c, value)
+                        .map_err(xlsx_error)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Default file name for an export, replacing the extension of the source file if any.
+pub fn file_name(title: &str, extension: &str) -> String {
+    let stem = Path::new(title)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(title);
+    format!("{}.{}", stem, extension)
+}
+
+/// Asks where to save the export. `None` if the dialog was cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_path(title: &str, format: &ExportFormat) -> Option<std::path::PathBuf> {
+    rfd::FileDialog::new()
+        .set_file_name(file_name(title, format.extension()))
+        .add_filter(format.name(), &[format.extension()])
+        .save_file()
+}
+
+/// Formats the frame as a Markdown table, e.g. for pasting into an issue or chat.
+pub fn to_markdown(df: &DataFrame) -> String {
+    let cell = |value: AnyValue<'_>| match value {
+        AnyValue::Null => String::new(),
+        AnyValue::String(s) => s.replace('|', "\\|"),
+        other => other.to_string().replace('|', "\\|"),
+    };
+    let mut lines = vec![
+        format!("| {} |", df.get_column_names().join(" | ")),
+        format!("|{}", " --- |".repeat(df.width())),
+    ];
+    for idx in 0..df.height() {
+        let row: Vec<String> = df
+            .get_columns()
+            .iter()
+            .map(|s| s.get(idx).map(cell).unwrap_or_default())
+            .collect();
+        lines.push(format!("| {} |", row.join(" | ")));
+    }
+    lines.join("\n")
+}
+
+/// Hands the exported bytes to the browser as a file download.
+#[cfg(target_arch = "wasm32")]
+pub fn download(title: &str, format: &ExportFormat, bytes: Vec<u8>) {
+    let dialog = rfd::AsyncFileDialog::new()
+        .set_file_name(file_name(title, format.extension()))
+        .add_filter(format.name(), &[format.extension()]);
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Some(handle) = dialog.save_file().await {
+            if let Err(e) = handle.write(&bytes).await {
+                log::error!("Could not download export: {}", e);
+            }
+        }
+    });
+}
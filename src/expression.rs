@@ -0,0 +1,236 @@
+use egui::{ComboBox, TextEdit};
+use polars::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StepOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Eq,
+    NotEq,
+    Gt,
+    GtEq,
+    Lt,
+    LtEq,
+    And,
+    Or,
+    Not,
+    Abs,
+    IsNull,
+    IsNotNull,
+}
+
+impl StepOp {
+    pub const ALL: [StepOp; 16] = [
+        StepOp::Add,
+        StepOp::Sub,
+        StepOp::Mul,
+        StepOp::Div,
+        StepOp::Eq,
+        StepOp::NotEq,
+        StepOp::Gt,
+        StepOp::GtEq,
+        StepOp::Lt,
+        StepOp::LtEq,
+        StepOp::And,
+        StepOp::Or,
+        StepOp::Not,
+        StepOp::Abs,
+        StepOp::IsNull,
+        StepOp::IsNotNull,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StepOp::Add => "+",
+            StepOp::Sub => "-",
+            StepOp::Mul => "*",
+            StepOp::Div => "/",
+            StepOp::Eq => "==",
+            StepOp::NotEq => "!=",
+            StepOp::Gt => ">",
+            StepOp::GtEq => ">=",
+            StepOp::Lt => "<",
+            StepOp::LtEq => "<=",
+            StepOp::And => "and",
+            StepOp::Or => "or",
+            StepOp::Not => "not",
+            StepOp::Abs => "abs",
+            StepOp::IsNull => "is null",
+            StepOp::IsNotNull => "is not null",
+        }
+    }
+
+    /// Whether the operation takes an operand, e.g. `+`, or only applies to the expression
+    /// built so far, e.g. `abs`.
+    pub fn binary(&self) -> bool {
+        !matches!(
+            self,
+            StepOp::Not | StepOp::Abs | StepOp::IsNull | StepOp::IsNotNull
+        )
+    }
+}
+
+/// The right side of a step: another column or a value typed in.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    Column(String),
+    Value(String),
+}
+
+impl Operand {
+    /// Values are numbers, booleans or otherwise text.
+    fn expr(&self) -> Expr {
+        match self {
+            Operand::Column(name) => col(name),
+            Operand::Value(value) => match (value.parse::<i64>(), value.parse::<f64>()) {
+                (Ok(n), _) => lit(n),
+                (_, Ok(n)) => lit(n),
+                _ => match value.as_str() {
+                    "true" => lit(true),
+                    "false" => lit(false),
+                    _ => lit(value.clone()),
+                },
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExprStep {
+    pub op: StepOp,
+    pub operand: Operand,
+}
+
+/// An expression built from a column and a chain of steps applied in order, e.g. `price`,
+/// `* qty`, `> 100`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ExprBuilder {
+    pub column: String,
+    pub steps: Vec<ExprStep>,
+}
+
+impl ExprBuilder {
+    pub fn build(&self) -> PolarsResult<Expr> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column the expression starts from");
+        }
+        let mut expr = col(&self.column);
+        for step in &self.steps {
+            if let Operand::Column(name) = &step.operand {
+                if step.op.binary() && name.is_empty() {
+                    polars_bail!(ComputeError: "pick the column of the '{}' step", step.op.label());
+                }
+            }
+            let rhs = step.operand.expr();
+            expr = match step.op {
+                StepOp::Add => expr + rhs,
+                StepOp::Sub => expr - rhs,
+                StepOp::Mul => expr * rhs,
+                StepOp::Div => binary_expr(expr, Operator::TrueDivide, rhs),
+                StepOp::Eq => expr.eq(rhs),
+                StepOp::NotEq => expr.neq(rhs),
+                StepOp::Gt => expr.gt(rhs),
+                StepOp::GtEq => expr.gt_eq(rhs),
+                StepOp::Lt => expr.lt(rhs),
+                StepOp::LtEq => expr.lt_eq(rhs),
+                StepOp::And => expr.and(rhs),
+                StepOp::Or => expr.or(rhs),
+                StepOp::Not => expr.not(),
+                StepOp::Abs => when(expr.clone().lt(lit(0)))
+                    .then(lit(0) - expr.clone())
+                    .otherwise(expr),
+                StepOp::IsNull => expr.is_null(),
+                StepOp::IsNotNull => expr.is_not_null(),
+            };
+        }
+        Ok(expr)
+    }
+
+    /// The expression as text, with each step wrapping the previous ones.
+    pub fn describe(&self) -> String {
+        let mut text = self.column.clone();
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                text = format!("({})", text);
+            }
+            text = match (&step.operand, step.op.binary()) {
+                (_, false) => format!("{} {}", text, step.op.label()),
+                (Operand::Column(name), true) => format!("{} {} {}", text, step.op.label(), name),
+                (Operand::Value(value), true) => match step.operand.expr() {
+                    Expr::Literal(LiteralValue::String(_)) => {
+                        format!("{} {} {:?}", text, step.op.label(), value)
+                    }
+                    _ => format!("{} {} {}", text, step.op.label(), value),
+                },
+            };
+        }
+        text
+    }
+
+    /// Editor with the start column and a row per step. `id` keeps the widgets of builders
+    /// shown in the same window apart.
+    pub fn show(&mut self, id: &str, columns: &[String], ui: &mut egui::Ui) {
+        ComboBox::from_id_source((id, "column"))
+            .selected_text(&self.column)
+            .show_ui(ui, |ui| {
+                for column in columns {
+                    ui.selectable_value(&mut self.column, column.to_owned(), column);
+                }
+            });
+        let mut remove = None;
+        for (i, step) in self.steps.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                ComboBox::from_id_source((id, "op", i))
+                    .width(60.0)
+                    .selected_text(step.op.label())
+                    .show_ui(ui, |ui| {
+                        for op in StepOp::ALL {
+                            ui.selectable_value(&mut step.op, op, op.label());
+                        }
+                    });
+                if step.op.binary() {
+                    let mut is_column = matches!(step.operand, Operand::Column(_));
+                    let column = ui.radio_value(&mut is_column, true, "Column").changed();
+                    let value = ui.radio_value(&mut is_column, false, "Value").changed();
+                    if column || value {
+                        step.operand = match is_column {
+                            true => Operand::Column(String::new()),
+                            false => Operand::Value(String::new()),
+                        };
+                    }
+                    match &mut step.operand {
+                        Operand::Column(name) => {
+                            ComboBox::from_id_source((id, "operand", i))
+                                .selected_text(name.as_str())
+                                .show_ui(ui, |ui| {
+                                    for column in columns {
+                                        ui.selectable_value(name, column.to_owned(), column);
+                                    }
+                                });
+                        }
+                        Operand::Value(value) => {
+                            ui.add(TextEdit::singleline(value).desired_width(80.0));
+                        }
+                    }
+                }
+                if ui.button("✖").clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.steps.remove(i);
+        }
+        if ui.button("Add step").clicked() {
+            self.steps.push(ExprStep {
+                op: StepOp::Add,
+                operand: Operand::Value(String::new()),
+            });
+        }
+        if !self.column.is_empty() {
+            ui.label(egui::RichText::new(self.describe()).monospace());
+        }
+    }
+}
@@ -1,34 +1,559 @@
+use crate::expression::ExprBuilder;
+use egui::{ComboBox, TextEdit};
 use polars::prelude::*;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum FilterOps {
     EqualNum,
     EqualStr,
+    NotEqualNum,
+    NotEqualStr,
     GreaterThan,
     GreaterEqualThan,
     LowerThan,
     LowerEqualThan,
     IsNull,
     IsNotNull,
+    Contains,
+    StartsWith,
+    EndsWith,
+    RegexMatch,
+    IsIn,
+    NotIn,
+    Between,
 }
 
+impl FilterOps {
+    pub const ALL: [FilterOps; 17] = [
+        FilterOps::EqualNum,
+        FilterOps::EqualStr,
+        FilterOps::NotEqualNum,
+        FilterOps::NotEqualStr,
+        FilterOps::GreaterThan,
+        FilterOps::GreaterEqualThan,
+        FilterOps::LowerThan,
+        FilterOps::LowerEqualThan,
+        FilterOps::IsNull,
+        FilterOps::IsNotNull,
+        FilterOps::Contains,
+        FilterOps::StartsWith,
+        FilterOps::EndsWith,
+        FilterOps::RegexMatch,
+        FilterOps::IsIn,
+        FilterOps::NotIn,
+        FilterOps::Between,
+    ];
+
+    /// Whether the operation can be applied to a column of the type. Comparisons also apply
+    /// to dates and times, typed as text.
+    pub fn applies_to(&self, dtype: &DataType) -> bool {
+        match self {
+            FilterOps::EqualNum | FilterOps::NotEqualNum => dtype.is_numeric(),
+            FilterOps::GreaterThan
+            | FilterOps::GreaterEqualThan
+            | FilterOps::LowerThan
+            | FilterOps::LowerEqualThan => dtype.is_numeric() || dtype.is_temporal(),
+            FilterOps::EqualStr | FilterOps::NotEqualStr => {
+                matches!(dtype, DataType::String | DataType::Categorical(_, _))
+            }
+            FilterOps::Contains
+            | FilterOps::StartsWith
+            | FilterOps::EndsWith
+            | FilterOps::RegexMatch => dtype == &DataType::String,
+            FilterOps::IsNull
+            | FilterOps::IsNotNull
+            | FilterOps::IsIn
+            | FilterOps::NotIn
+            | FilterOps::Between => true,
+        }
+    }
+
+    /// Whether the value is compared as a number.
+    fn numeric(&self) -> bool {
+        matches!(
+            self,
+            FilterOps::EqualNum
+                | FilterOps::NotEqualNum
+                | FilterOps::GreaterThan
+                | FilterOps::GreaterEqualThan
+                | FilterOps::LowerThan
+                | FilterOps::LowerEqualThan
+        )
+    }
+}
+
+/// A single predicate on a column, e.g. "price GreaterThan 100".
 #[derive(Clone, Debug, PartialEq)]
-pub struct DataFrameFilter {
+pub struct FilterCondition {
     pub column: String,
     pub operation: FilterOps,
     pub value: String,
-    pub inplace: bool,
-    pub filtered_data: Option<DataFrame>,
+    /// Upper bound of a Between filter, `value` being the lower one.
+    pub upper: String,
+    pub lower_inclusive: bool,
+    pub upper_inclusive: bool,
+    /// Keep the rows the operation doesn't match.
+    pub negate: bool,
 }
 
-impl Default for DataFrameFilter {
+impl Default for FilterCondition {
     fn default() -> Self {
         Self {
             column: String::from(""),
             operation: FilterOps::EqualNum,
             value: String::from(""),
-            inplace: false,
-            filtered_data: None,
+            upper: String::from(""),
+            lower_inclusive: true,
+            upper_inclusive: true,
+            negate: false,
+        }
+    }
+}
+
+impl FilterCondition {
+    pub fn new(column: String, operation: FilterOps, value: String) -> Self {
+        Self {
+            column,
+            operation,
+            value,
+            ..Default::default()
+        }
+    }
+
+    /// The predicate as an expression, negated if set.
+    pub fn expr(&self, schema: &Schema) -> PolarsResult<Expr> {
+        let expr = self.operation_expr(schema)?;
+        Ok(match self.negate {
+            // nulls don't match the operation, so they are kept when negated.
+            true => expr.fill_null(false).not(),
+            false => expr,
+        })
+    }
+
+    /// Why the condition can't be applied to a column of the type, if it can't: the operation
+    /// doesn't apply to it or the value isn't a number.
+    pub fn check(&self, dtype: &DataType) -> Option<String> {
+        if !self.operation.applies_to(dtype) {
+            return Some(format!(
+                "{:?} doesn't apply to '{}', which is {}",
+                self.operation, self.column, dtype
+            ));
+        }
+        if self.operation.numeric()
+            && dtype.is_numeric()
+            && self.value.trim().parse::<f64>().is_err()
+        {
+            return Some(format!("'{}' is not a number", self.value.trim()));
+        }
+        None
+    }
+
+    /// Values of comparisons with dates, and of IsIn and Between filters, are parsed to the
+    /// column type, so dates can be typed as text.
+    fn operation_expr(&self, schema: &Schema) -> PolarsResult<Expr> {
+        let column = self.column.as_str();
+        let value = self.value.as_str();
+        let dtype = schema.try_get(column)?;
+        if let Some(error) = self.check(dtype) {
+            polars_bail!(ComputeError: "{}", error);
+        }
+        let number = match dtype.is_numeric() {
+            true => lit(value.trim().parse::<f64>().unwrap_or_default()),
+            false => lit(value.trim().to_string()).strict_cast(dtype.clone()),
+        };
+        let text = lit(value.to_string());
+        Ok(match self.operation {
+            FilterOps::EqualNum => col(column).eq(number),
+            FilterOps::EqualStr => col(column).eq(text),
+            FilterOps::NotEqualNum => col(column).neq_missing(number),
+            FilterOps::NotEqualStr => col(column).neq_missing(text),
+            FilterOps::GreaterThan => col(column).gt(number),
+            FilterOps::GreaterEqualThan => col(column).gt_eq(number),
+            FilterOps::LowerThan => col(column).lt(number),
+            FilterOps::LowerEqualThan => col(column).lt_eq(number),
+            FilterOps::IsNull => col(column).is_null(),
+            FilterOps::IsNotNull => col(column).is_not_null(),
+            FilterOps::Contains => col(column).str().contains_literal(text),
+            FilterOps::StartsWith => col(column).str().starts_with(text),
+            FilterOps::EndsWith => col(column).str().ends_with(text),
+            FilterOps::RegexMatch => col(column).str().contains(text, true),
+            FilterOps::IsIn | FilterOps::NotIn => {
+                let values = Series::new("", split_values(value)).strict_cast(dtype)?;
+                let is_in = col(column).is_in(lit(values)).fill_null(false);
+                match self.operation {
+                    FilterOps::IsIn => is_in,
+                    _ => is_in.not(),
+                }
+            }
+            FilterOps::Between => {
+                // an empty bound leaves that side open.
+                let bound = |text: &str| lit(text.trim().to_string()).strict_cast(dtype.clone());
+                let mut predicate = lit(true);
+                if !value.trim().is_empty() {
+                    predicate = predicate.and(match self.lower_inclusive {
+                        true => col(column).gt_eq(bound(value)),
+                        false => col(column).gt(bound(value)),
+                    });
+                }
+                if !self.upper.trim().is_empty() {
+                    predicate = predicate.and(match self.upper_inclusive {
+                        true => col(column).lt_eq(bound(&self.upper)),
+                        false => col(column).lt(bound(&self.upper)),
+                    });
+                }
+                predicate
+            }
+        })
+    }
+
+    /// A row of widgets for the condition. `df` holds the values offered for IsIn/NotIn.
+    /// Only the operations that apply to the type of the column are offered.
+    fn show(&mut self, id: egui::Id, columns: &[String], df: &DataFrame, ui: &mut egui::Ui) {
+        let dtype = df.column(&self.column).ok().map(|s| s.dtype().clone());
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.negate, "Not");
+            ComboBox::from_id_source(id.with("column"))
+                .selected_text(&self.column)
+                .show_ui(ui, |ui| {
+                    for col in columns {
+                        ui.selectable_value(&mut self.column, col.to_owned(), col);
+                    }
+                });
+            ComboBox::from_id_source(id.with("operation"))
+                .selected_text(format!("{:?}", &self.operation))
+                .show_ui(ui, |ui| {
+                    for operation in FilterOps::ALL {
+                        if dtype.as_ref().is_some_and(|d| !operation.applies_to(d)) {
+                            continue;
+                        }
+                        let label = match operation {
+                            FilterOps::IsNull => String::from("Null"),
+                            _ => format!("{:?}", operation),
+                        };
+                        ui.selectable_value(&mut self.operation, operation, label);
+                    }
+                });
+            ui.add(TextEdit::singleline(&mut self.value).desired_width(100.0));
+            if self.operation == FilterOps::Between {
+                ui.label("and");
+                ui.add(TextEdit::singleline(&mut self.upper).desired_width(100.0));
+            }
+            if matches!(self.operation, FilterOps::IsIn | FilterOps::NotIn) {
+                ui.menu_button("⏷", |ui| self.pick_values(df, ui))
+                    .response
+                    .on_hover_text("Pick values of the column");
+            }
+        });
+        if self.operation == FilterOps::Between {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut self.lower_inclusive, "Include lower bound");
+                ui.checkbox(&mut self.upper_inclusive, "Include upper bound");
+            });
+        }
+        if let Some(error) = dtype.and_then(|d| self.check(&d)) {
+            ui.colored_label(ui.visuals().error_fg_color, error);
         }
     }
+
+    /// Checkboxes for the distinct values of the column, in the loaded rows, that add or
+    /// remove the value from the IsIn/NotIn value list.
+    fn pick_values(&mut self, df: &DataFrame, ui: &mut egui::Ui) {
+        let Ok(series) = df.column(&self.column) else {
+            ui.label("Pick a column first");
+            return;
+        };
+        let Ok(unique) = series.unique_stable() else {
+            return;
+        };
+        let mut values: Vec<String> = split_values(&self.value)
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let before = values.len();
+        egui::ScrollArea::vertical()
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for value in unique.iter().take(MAX_PICKED_VALUES) {
+                    let text = match value {
+                        AnyValue::Null => continue,
+                        AnyValue::String(s) => s.to_string(),
+                        v => v.to_string(),
+                    };
+                    let mut checked = values.contains(&text);
+                    if ui.checkbox(&mut checked, &text).changed() {
+                        match checked {
+                            true => values.push(text),
+                            false => values.retain(|v| v != &text),
+                        }
+                    }
+                }
+            });
+        if unique.len() > MAX_PICKED_VALUES {
+            ui.label(format!("First {} values", MAX_PICKED_VALUES));
+        }
+        if values.len() != before {
+            self.value = values.join(", ");
+        }
+    }
+}
+
+/// Most distinct values listed to pick the values of an IsIn/NotIn filter.
+const MAX_PICKED_VALUES: usize = 200;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FilterNode {
+    Condition(FilterCondition),
+    Group(FilterGroup),
+}
+
+/// Conditions and nested groups, all of which (AND) or any of which (OR) must hold.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterGroup {
+    pub any: bool,
+    /// Keep the rows the group doesn't match.
+    pub negate: bool,
+    pub nodes: Vec<FilterNode>,
+}
+
+impl Default for FilterGroup {
+    fn default() -> Self {
+        Self::single(FilterCondition::default())
+    }
+}
+
+impl FilterGroup {
+    pub fn single(condition: FilterCondition) -> Self {
+        Self {
+            any: false,
+            negate: false,
+            nodes: vec![FilterNode::Condition(condition)],
+        }
+    }
+
+    /// The group as a single expression. An empty group keeps every row.
+    pub fn expr(&self, schema: &Schema) -> PolarsResult<Expr> {
+        let mut exprs = Vec::new();
+        for node in &self.nodes {
+            exprs.push(match node {
+                FilterNode::Condition(condition) => condition.expr(schema)?,
+                FilterNode::Group(group) => group.expr(schema)?,
+            });
+        }
+        let expr = exprs
+            .into_iter()
+            .reduce(|a, b| match self.any {
+                true => a.or(b),
+                false => a.and(b),
+            })
+            .unwrap_or(lit(true));
+        Ok(match self.negate {
+            true => expr.fill_null(false).not(),
+            false => expr,
+        })
+    }
+
+    /// Calls `f` on every condition, including the ones of nested groups.
+    pub fn for_each_condition(&mut self, f: &mut impl FnMut(&mut FilterCondition)) {
+        for node in &mut self.nodes {
+            match node {
+                FilterNode::Condition(condition) => f(condition),
+                FilterNode::Group(group) => group.for_each_condition(f),
+            }
+        }
+    }
+
+    /// Editor with a row per condition and a frame per nested group.
+    pub fn show(&mut self, id: egui::Id, columns: &[String], df: &DataFrame, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Match");
+            ui.radio_value(&mut self.any, false, "All (AND)");
+            ui.radio_value(&mut self.any, true, "Any (OR)");
+            ui.checkbox(&mut self.negate, "Not");
+        });
+        let mut remove = None;
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            let id = id.with(i);
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").clicked() {
+                    remove = Some(i);
+                }
+                match node {
+                    FilterNode::Condition(condition) => {
+                        ui.vertical(|ui| condition.show(id, columns, df, ui));
+                    }
+                    FilterNode::Group(group) => {
+                        ui.group(|ui| {
+                            ui.vertical(|ui| group.show(id, columns, df, ui));
+                        });
+                    }
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.nodes.remove(i);
+        }
+        ui.horizontal(|ui| {
+            if ui.button("Add condition").clicked() {
+                self.nodes
+                    .push(FilterNode::Condition(FilterCondition::default()));
+            }
+            if ui.button("Add group").clicked() {
+                self.nodes.push(FilterNode::Group(FilterGroup::default()));
+            }
+        });
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameFilter {
+    /// Conditions of the filter, picked in the Filter section or in the data view.
+    pub root: FilterGroup,
+    pub inplace: bool,
+    pub filtered_data: Option<DataFrame>,
+    /// Condition built with the expression builder, for filters beyond a single operation.
+    pub builder: ExprBuilder,
+    pub error: Option<String>,
+}
+
+/// The values of a comma separated list, e.g. the value of an IsIn filter.
+pub fn split_values(value: &str) -> Vec<&str> {
+    value
+        .split(',')
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> DataFrame {
+        df! {
+            "id" => [1i64, 2, 3, 4, 5],
+            "x" => [Some(1.0f64), None, Some(3.0), Some(4.0), Some(5.0)],
+            "name" => [Some("a"), Some("b"), None, Some("ab"), Some("c")],
+        }
+        .unwrap()
+    }
+
+    fn condition(column: &str, operation: FilterOps, value: &str) -> FilterNode {
+        FilterNode::Condition(FilterCondition::new(
+            column.to_string(),
+            operation,
+            value.to_string(),
+        ))
+    }
+
+    fn group(any: bool, nodes: Vec<FilterNode>) -> FilterGroup {
+        FilterGroup {
+            any,
+            negate: false,
+            nodes,
+        }
+    }
+
+    /// The ids of the rows the filter keeps.
+    fn ids(group: &FilterGroup) -> Vec<i64> {
+        let df = frame();
+        let filtered = df
+            .clone()
+            .lazy()
+            .filter(group.expr(&df.schema()).unwrap())
+            .collect()
+            .unwrap();
+        let ids = filtered.column("id").unwrap().i64().unwrap();
+        ids.into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn empty_group_keeps_every_row() {
+        let mut empty = group(false, vec![]);
+        assert_eq!(ids(&empty), [1, 2, 3, 4, 5]);
+        empty.any = true;
+        assert_eq!(ids(&empty), [1, 2, 3, 4, 5]);
+        empty.negate = true;
+        assert_eq!(ids(&empty), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn nested_groups() {
+        let names = group(
+            true,
+            vec![
+                condition("name", FilterOps::EqualStr, "a"),
+                condition("name", FilterOps::EqualStr, "c"),
+            ],
+        );
+        assert_eq!(ids(&names), [1, 5]);
+        let root = group(
+            false,
+            vec![
+                condition("x", FilterOps::GreaterThan, "2"),
+                FilterNode::Group(names.clone()),
+            ],
+        );
+        assert_eq!(ids(&root), [5]);
+        let root = group(
+            true,
+            vec![
+                condition("x", FilterOps::GreaterThan, "3"),
+                FilterNode::Group(names),
+            ],
+        );
+        assert_eq!(ids(&root), [1, 4, 5]);
+    }
+
+    #[test]
+    fn negate_keeps_nulls() {
+        let mut greater =
+            FilterCondition::new(String::from("x"), FilterOps::GreaterThan, String::from("2"));
+        let root = group(false, vec![FilterNode::Condition(greater.clone())]);
+        assert_eq!(ids(&root), [3, 4, 5]);
+        // the row where x is null doesn't match, so "not" keeps it.
+        greater.negate = true;
+        let root = group(false, vec![FilterNode::Condition(greater)]);
+        assert_eq!(ids(&root), [1, 2]);
+        let mut negated = group(false, vec![condition("name", FilterOps::StartsWith, "a")]);
+        negated.negate = true;
+        assert_eq!(ids(&negated), [2, 3, 5]);
+        let root = group(false, vec![condition("x", FilterOps::NotEqualNum, "3")]);
+        assert_eq!(ids(&root), [1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn check_rejects_values_that_are_not_numbers() {
+        let condition =
+            FilterCondition::new(String::from("x"), FilterOps::EqualNum, String::from("abc"));
+        assert_eq!(
+            condition.check(&DataType::Float64),
+            Some(String::from("'abc' is not a number"))
+        );
+        assert!(condition.expr(&frame().schema()).is_err());
+        let condition = FilterCondition::new(
+            String::from("x"),
+            FilterOps::LowerThan,
+            String::from(" 2.5 "),
+        );
+        assert_eq!(condition.check(&DataType::Float64), None);
+    }
+
+    #[test]
+    fn check_rejects_operations_for_other_types() {
+        let contains =
+            FilterCondition::new(String::from("x"), FilterOps::Contains, String::from("1"));
+        assert!(contains.check(&DataType::Float64).is_some());
+        assert!(contains.check(&DataType::String).is_none());
+        let greater = FilterCondition::new(
+            String::from("name"),
+            FilterOps::GreaterThan,
+            String::from("1"),
+        );
+        assert!(greater.check(&DataType::String).is_some());
+        assert!(greater.check(&DataType::Date).is_none());
+        let equal =
+            FilterCondition::new(String::from("name"), FilterOps::EqualStr, String::from("a"));
+        assert!(equal.check(&DataType::Int64).is_some());
+        assert!(FilterOps::IsNull.applies_to(&DataType::Boolean));
+    }
 }
@@ -0,0 +1,319 @@
+use crate::expression::ExprBuilder;
+use polars::prelude::*;
+
+/// A column computed from a formula over the other columns, e.g. `price * qty` or
+/// `first_name + " " + last_name`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameFormula {
+    pub name: String,
+    pub formula: String,
+    /// Use the expression builder instead of the formula.
+    pub use_builder: bool,
+    pub builder: ExprBuilder,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameFormula {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.name.trim().is_empty() {
+            polars_bail!(ComputeError: "the new column needs a name");
+        }
+        let expr = match self.use_builder {
+            true => self.builder.build()?,
+            false => parse_formula(&self.formula)?,
+        };
+        df.with_column(expr.alias(self.name.trim())).collect()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Number(f64),
+    Text(String),
+    Column(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number(n) => write!(f, "{}", n),
+            Token::Text(text) => write!(f, "\"{}\"", text),
+            Token::Column(name) => write!(f, "{}", name),
+            Token::Op(op) => write!(f, "{}", op),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+        }
+    }
+}
+
+const OPERATORS: [&str; 13] = [
+    ">=", "<=", "==", "!=", "&&", "||", "+", "-", "*", "/", "%", ">", "<",
+];
+
+fn tokenize(formula: &str) -> PolarsResult<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = formula.char_indices().peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+        } else if c == '"' || c == '\'' || c == '`' {
+            // quotes are literals, backticks column names with spaces or symbols.
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some((_, q)) if q == c => break,
+                    Some((_, ch)) => text.push(ch),
+                    None => polars_bail!(ComputeError: "unclosed {} in the formula", c),
+                }
+            }
+            tokens.push(match c {
+                '`' => Token::Column(text),
+                _ => Token::Text(text),
+            });
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut number = String::new();
+            while let Some(&(_, ch)) = chars.peek() {
+                if !(ch.is_ascii_digit() || ch == '.') {
+                    break;
+                }
+                number.push(ch);
+                chars.next();
+            }
+            match number.parse() {
+                Ok(n) => tokens.push(Token::Number(n)),
+                Err(_) => polars_bail!(ComputeError: "'{}' is not a number", number),
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            let mut word = String::new();
+            while let Some(&(_, ch)) = chars.peek() {
+                if !(ch.is_alphanumeric() || ch == '_') {
+                    break;
+                }
+                word.push(ch);
+                chars.next();
+            }
+            tokens.push(Token::Column(word));
+        } else {
+            match OPERATORS.iter().find(|op| formula[i..].starts_with(*op)) {
+                Some(op) => {
+                    for _ in 0..op.len() {
+                        chars.next();
+                    }
+                    tokens.push(Token::Op(op));
+                }
+                None => polars_bail!(ComputeError: "unexpected '{}' in the formula", c),
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Recursive descent over the tokens, one method per precedence level.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next_op(&mut self, ops: &[&str]) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) if ops.contains(op) => {
+                self.pos += 1;
+                Some(op)
+            }
+            _ => None,
+        }
+    }
+
+    fn or(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.and()?;
+        while self.next_op(&["||"]).is_some() {
+            expr = expr.or(self.and()?);
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.comparison()?;
+        while self.next_op(&["&&"]).is_some() {
+            expr = expr.and(self.comparison()?);
+        }
+        Ok(expr)
+    }
+
+    fn comparison(&mut self) -> PolarsResult<Expr> {
+        let expr = self.sum()?;
+        Ok(match self.next_op(&[">=", "<=", "==", "!=", ">", "<"]) {
+            Some(">=") => expr.gt_eq(self.sum()?),
+            Some("<=") => expr.lt_eq(self.sum()?),
+            Some("==") => expr.eq(self.sum()?),
+            Some("!=") => expr.neq(self.sum()?),
+            Some(">") => expr.gt(self.sum()?),
+            Some(_) => expr.lt(self.sum()?),
+            None => expr,
+        })
+    }
+
+    fn sum(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.product()?;
+        while let Some(op) = self.next_op(&["+", "-"]) {
+            let rhs = self.product()?;
+            expr = match op {
+                "+" => expr + rhs,
+                _ => expr - rhs,
+            };
+        }
+        Ok(expr)
+    }
+
+    fn product(&mut self) -> PolarsResult<Expr> {
+        let mut expr = self.unary()?;
+        while let Some(op) = self.next_op(&["*", "/", "%"]) {
+            let rhs = self.unary()?;
+            expr = match op {
+                "*" => expr * rhs,
+                // true division, so that `qty / 2` isn't rounded down.
+                "/" => binary_expr(expr, Operator::TrueDivide, rhs),
+                _ => expr % rhs,
+            };
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> PolarsResult<Expr> {
+        match self.next_op(&["-"]) {
+            Some(_) => Ok(lit(0) - self.unary()?),
+            None => self.atom(),
+        }
+    }
+
+    fn atom(&mut self) -> PolarsResult<Expr> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Number(n)) if n.fract() == 0.0 && n.abs() < i64::MAX as f64 => {
+                Ok(lit(n as i64))
+            }
+            Some(Token::Number(n)) => Ok(lit(n)),
+            Some(Token::Text(text)) => Ok(lit(text)),
+            // there are no functions, `name(` is a typo for a function the formula doesn't have.
+            Some(Token::Column(name)) if self.tokens.get(self.pos) == Some(&Token::Open) => {
+                polars_bail!(ComputeError: "unknown function '{}' in the formula", name)
+            }
+            Some(Token::Column(name)) => Ok(col(&name)),
+            Some(Token::Open) => {
+                let expr = self.or()?;
+                match self.tokens.get(self.pos) {
+                    Some(Token::Close) => {
+                        self.pos += 1;
+                        Ok(expr)
+                    }
+                    _ => polars_bail!(ComputeError: "missing ')' in the formula"),
+                }
+            }
+            Some(token @ (Token::Close | Token::Op(_))) => {
+                polars_bail!(ComputeError: "unexpected '{}' in the formula", token)
+            }
+            None => polars_bail!(ComputeError: "the formula is incomplete"),
+        }
+    }
+}
+
+/// Parses a formula into an expression. Bare words and `quoted names` are columns, "text"
+/// and numbers are literals, with arithmetic, comparison and `&&`/`||` operators.
+pub fn parse_formula(formula: &str) -> PolarsResult<Expr> {
+    let tokens = tokenize(formula)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let expr = parser.or()?;
+    if parser.pos < parser.tokens.len() {
+        polars_bail!(ComputeError: "unexpected '{}' in the formula", parser.tokens[parser.pos]);
+    }
+    Ok(expr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> DataFrame {
+        df! {
+            "a" => [1i64, 2, 3],
+            "b" => [10i64, 20, 30],
+            "unit price" => [0.5f64, 1.5, 2.5],
+        }
+        .unwrap()
+    }
+
+    fn eval(formula: &str) -> Series {
+        let expr = parse_formula(formula).unwrap();
+        let out = frame()
+            .lazy()
+            .select([expr.alias("out")])
+            .collect()
+            .unwrap();
+        out.column("out").unwrap().clone()
+    }
+
+    fn error(formula: &str) -> String {
+        parse_formula(formula).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn precedence() {
+        assert_eq!(eval("a + b * 2"), Series::new("out", [21i64, 42, 63]));
+        assert_eq!(eval("(a + b) * 2"), Series::new("out", [22i64, 44, 66]));
+        assert_eq!(eval("b - a - 1"), Series::new("out", [8i64, 17, 26]));
+        assert_eq!(
+            eval("a + 1 > 2 && b < 30 || a == 3"),
+            Series::new("out", [false, true, true])
+        );
+    }
+
+    #[test]
+    fn unary_minus() {
+        assert_eq!(eval("-a"), Series::new("out", [-1i64, -2, -3]));
+        assert_eq!(eval("-a * 2"), Series::new("out", [-2i64, -4, -6]));
+        assert_eq!(eval("b - -a"), Series::new("out", [11i64, 22, 33]));
+        assert_eq!(eval("-(a + b)"), Series::new("out", [-11i64, -22, -33]));
+    }
+
+    #[test]
+    fn quoted_column_names() {
+        assert_eq!(
+            eval("`unit price` * 2"),
+            Series::new("out", [1.0f64, 3.0, 5.0])
+        );
+        assert_eq!(
+            eval("\"x\" + 'y'"),
+            Series::new("out", [String::from("xy")])
+        );
+    }
+
+    #[test]
+    fn int_and_float_literals() {
+        assert_eq!(eval("a + 1").dtype(), &DataType::Int64);
+        assert_eq!(eval("a + 1.5").dtype(), &DataType::Float64);
+        // division is never rounded down.
+        assert_eq!(eval("a / 2"), Series::new("out", [0.5f64, 1.0, 1.5]));
+    }
+
+    #[test]
+    fn errors() {
+        assert!(error("(a + b").contains("missing ')'"));
+        assert!(error("a + b)").contains("unexpected ')'"));
+        assert!(error("a +").contains("incomplete"));
+        assert!(error("a * / b").contains("unexpected '/'"));
+        assert!(error("sqrt(a)").contains("unknown function 'sqrt'"));
+        assert!(error("`a").contains("unclosed"));
+        assert!(error("1.2.3").contains("not a number"));
+        assert!(error("a $ b").contains("unexpected '$'"));
+    }
+}
@@ -1,5 +1,9 @@
+use crate::utils::is_duration;
 use polars::prelude::*;
 
+/// Rows of a cross join above which it has to be confirmed.
+pub const CROSS_JOIN_CONFIRM_ROWS: usize = 10_000_000;
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct DataFrameJoin {
     pub df_selection: String,
@@ -8,9 +12,24 @@ pub struct DataFrameJoin {
     pub right_on_selection: String,
     pub right_on_cols: Vec<String>,
     pub how: JoinType,
+    /// Which right row an as-of join picks for each left row.
+    pub asof_strategy: AsofStrategy,
+    /// Largest distance between the keys of an as-of join, a number or a duration like "5m".
+    pub tolerance: String,
+    /// Columns that must also match exactly in an as-of join, e.g. the ticker of a trade.
+    pub asof_by: Vec<String>,
+    /// Added to the right columns whose names are already on the left.
+    pub suffix: String,
+    /// Drop the right columns whose names are already on the left instead of suffixing them.
+    pub drop_duplicates: bool,
     pub joindata: Option<DataFrame>,
     pub join: bool,
+    /// Rows of a cross join waiting to be confirmed.
+    pub cross_rows: Option<usize>,
+    /// The cross join was confirmed, whatever its size.
+    pub confirmed: bool,
     pub inplace: bool,
+    pub error: Option<String>,
 }
 
 impl Default for DataFrameJoin {
@@ -22,9 +41,158 @@ impl Default for DataFrameJoin {
             right_on_selection: String::default(),
             right_on_cols: Vec::new(),
             how: JoinType::Inner,
+            asof_strategy: AsofStrategy::Backward,
+            tolerance: String::default(),
+            asof_by: Vec::new(),
+            suffix: String::from("_right"),
+            drop_duplicates: false,
             joindata: None,
             join: false,
+            cross_rows: None,
+            confirmed: false,
             inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameJoin {
+    pub fn is_asof(&self) -> bool {
+        matches!(self.how, JoinType::AsOf(_))
+    }
+
+    /// `key` is the type of the left key, durations only apply to temporal keys.
+    fn asof_options(&self, key: &DataType) -> PolarsResult<AsOfOptions> {
+        let tolerance = self.tolerance.trim();
+        let mut options = AsOfOptions {
+            strategy: self.asof_strategy,
+            ..Default::default()
+        };
+        if !tolerance.is_empty() {
+            let number = match tolerance.parse::<i64>() {
+                Ok(n) => Some(AnyValue::Int64(n)),
+                Err(_) => tolerance.parse::<f64>().ok().map(AnyValue::Float64),
+            };
+            if let Some(number) = number {
+                // the tolerance is compared to the keys as they are stored, e.g. days for a Date.
+                let physical = key.to_physical();
+                if !physical.is_numeric() {
+                    polars_bail!(
+                        ComputeError: "'{}' is {}, it can't have a tolerance", self.left_on_selection, key
+                    );
+                }
+                if number.extract::<f64>().map_or(true, |n| n < 0.0) {
+                    polars_bail!(ComputeError: "the tolerance can't be negative");
+                }
+                let cast = number
+                    .strict_cast(&physical)
+                    .filter(|cast| cast.extract::<f64>() == number.extract::<f64>());
+                match cast {
+                    Some(cast) => options.tolerance = Some(cast.into_static()?),
+                    None => polars_bail!(
+                        ComputeError: "the tolerance {} doesn't fit '{}', which is {}", tolerance, self.left_on_selection, key
+                    ),
+                }
+            } else if is_duration(tolerance) {
+                if !key.is_temporal() {
+                    polars_bail!(
+                        ComputeError: "a duration tolerance needs a date or time key, '{}' is {}", self.left_on_selection, key
+                    );
+                }
+                options.tolerance_str = Some(tolerance.into());
+            } else {
+                polars_bail!(
+                    ComputeError: "the tolerance '{}' is neither a number nor a duration like 5m or 1h30m", tolerance
+                );
+            }
+        }
+        if !self.asof_by.is_empty() {
+            options.left_by = Some(self.asof_by.iter().map(|c| c.into()).collect());
+            options.right_by = Some(self.asof_by.iter().map(|c| c.into()).collect());
+        }
+        Ok(options)
+    }
+
+    pub fn apply(&self, mut left: LazyFrame, mut right: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.drop_duplicates {
+            let left_schema = left.schema()?;
+            let keep: Vec<Expr> = right
+                .schema()?
+                .iter_names()
+                .filter(|c| {
+                    !left_schema.contains(c)
+                        || **c == self.right_on_selection
+                        || (self.is_asof() && self.asof_by.iter().any(|b| b == c.as_str()))
+                })
+                .map(|c| col(c))
+                .collect();
+            right = right.select(keep);
+        } else if self.suffix.is_empty() {
+            polars_bail!(ComputeError: "the suffix for duplicate column names can't be empty");
         }
+        let (left, right, how) = match self.is_asof() {
+            // as-of joins need both sides sorted by the key.
+            true => {
+                let key = left.schema()?.try_get(&self.left_on_selection)?.clone();
+                (
+                    left.sort([&self.left_on_selection], Default::default()),
+                    right.sort([&self.right_on_selection], Default::default()),
+                    JoinType::AsOf(self.asof_options(&key)?),
+                )
+            }
+            false => (left, right, self.how.clone()),
+        };
+        left.join(
+            right,
+            [col(&self.left_on_selection)],
+            [col(&self.right_on_selection)],
+            JoinArgs::new(how).with_suffix(Some(self.suffix.clone())),
+        )
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn join(tolerance: &str) -> DataFrameJoin {
+        DataFrameJoin {
+            left_on_selection: String::from("key"),
+            how: JoinType::AsOf(AsOfOptions::default()),
+            tolerance: String::from(tolerance),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tolerance_is_cast_to_the_key() {
+        let options = join("5").asof_options(&DataType::Int32).unwrap();
+        assert_eq!(options.tolerance, Some(AnyValue::Int32(5)));
+        let options = join("2.5").asof_options(&DataType::Float64).unwrap();
+        assert_eq!(options.tolerance, Some(AnyValue::Float64(2.5)));
+        // a Date is stored as days.
+        let options = join("3").asof_options(&DataType::Date).unwrap();
+        assert_eq!(options.tolerance, Some(AnyValue::Int32(3)));
+        let options = join("").asof_options(&DataType::Int64).unwrap();
+        assert_eq!(options.tolerance, None);
+    }
+
+    #[test]
+    fn tolerance_that_does_not_fit_is_rejected() {
+        assert!(join("1.5").asof_options(&DataType::Int64).is_err());
+        assert!(join("300").asof_options(&DataType::Int8).is_err());
+        assert!(join("-1").asof_options(&DataType::Int64).is_err());
+        assert!(join("-0.5").asof_options(&DataType::Float64).is_err());
+        assert!(join("1").asof_options(&DataType::String).is_err());
+        assert!(join("soon").asof_options(&DataType::Int64).is_err());
+    }
+
+    #[test]
+    fn duration_tolerance_needs_a_temporal_key() {
+        let datetime = DataType::Datetime(TimeUnit::Microseconds, None);
+        let options = join("1h30m").asof_options(&datetime).unwrap();
+        assert_eq!(options.tolerance_str.as_deref(), Some("1h30m"));
+        assert!(join("1h30m").asof_options(&DataType::Int64).is_err());
     }
 }
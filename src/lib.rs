@@ -1,10 +1,40 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod aggregate;
+mod anonymize;
 mod app;
+#[cfg(not(target_arch = "wasm32"))]
+mod background;
+mod clean;
+mod columns;
+mod concat;
 mod container;
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "postgres", feature = "mysql", feature = "sqlite")
+))]
+mod database;
+mod dates;
+mod editor;
+mod encode;
+mod export;
+mod expression;
 mod filter;
+mod formula;
+mod import;
 mod join;
 mod melt;
+mod pivot;
+mod reader;
+mod rules;
+mod sample;
+mod slice;
+mod sort;
+mod sql;
+mod strings;
+mod table;
 mod utils;
+#[cfg(not(target_arch = "wasm32"))]
+mod watcher;
+mod window;
 pub use app::App;
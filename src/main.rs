@@ -6,6 +6,7 @@
 fn main() -> eframe::Result<()> {
     // Log to stdout (if you run with `RUST_LOG=debug`).
     //tracing_subscriber::fmt::init();
+    env_logger::init();
 
     let native_options = eframe::NativeOptions::default();
 
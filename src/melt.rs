@@ -1,24 +1,42 @@
 use polars::prelude::*;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct DataFrameMelt {
     pub id_selection: String,
     pub val_selection: String,
     pub id_vars: Vec<String>,
     pub value_vars: Vec<String>,
+    /// Melt every column that isn't an ID var instead of the value vars.
+    pub all_remaining: bool,
+    /// Names of the melted columns, "variable" and "value" if empty.
+    pub variable_name: String,
+    pub value_name: String,
     pub meltdata: Option<DataFrame>,
     pub display: bool,
 }
 
-impl Default for DataFrameMelt {
-    fn default() -> Self {
-        Self {
-            id_selection: String::default(),
-            val_selection: String::default(),
-            id_vars: Vec::new(),
-            value_vars: Vec::new(),
-            meltdata: None,
-            display: false,
+impl DataFrameMelt {
+    /// The name typed in, or None for the default name.
+    fn name(name: &str) -> Option<SmartString> {
+        let name = name.trim();
+        (!name.is_empty()).then(|| name.into())
+    }
+
+    pub fn args(&self, columns: &[String]) -> UnpivotArgs {
+        let on = match self.all_remaining {
+            true => columns
+                .iter()
+                .filter(|c| !self.id_vars.contains(c))
+                .map(|c| c.into())
+                .collect(),
+            false => self.value_vars.iter().map(|c| c.into()).collect(),
+        };
+        UnpivotArgs {
+            on,
+            index: self.id_vars.iter().map(|c| c.into()).collect(),
+            variable_name: Self::name(&self.variable_name),
+            value_name: Self::name(&self.value_name),
+            ..Default::default()
         }
     }
 }
@@ -0,0 +1,142 @@
+use polars::prelude::pivot::pivot_stable;
+use polars::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PivotFunc {
+    First,
+    Last,
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+}
+
+impl PivotFunc {
+    pub const ALL: [PivotFunc; 8] = [
+        PivotFunc::First,
+        PivotFunc::Last,
+        PivotFunc::Count,
+        PivotFunc::Sum,
+        PivotFunc::Mean,
+        PivotFunc::Median,
+        PivotFunc::Min,
+        PivotFunc::Max,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PivotFunc::First => "First",
+            PivotFunc::Last => "Last",
+            PivotFunc::Count => "Count",
+            PivotFunc::Sum => "Sum",
+            PivotFunc::Mean => "Mean",
+            PivotFunc::Median => "Median",
+            PivotFunc::Min => "Min",
+            PivotFunc::Max => "Max",
+        }
+    }
+
+    fn apply(&self, expr: Expr) -> Expr {
+        match self {
+            PivotFunc::First => expr.first(),
+            PivotFunc::Last => expr.last(),
+            PivotFunc::Count => expr.count(),
+            PivotFunc::Sum => expr.sum(),
+            PivotFunc::Mean => expr.mean(),
+            PivotFunc::Median => expr.median(),
+            PivotFunc::Min => expr.min(),
+            PivotFunc::Max => expr.max(),
+        }
+    }
+}
+
+/// The inverse of a melt: the values of the `on` column become new columns, holding the
+/// aggregated `values` of each combination with the index columns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFramePivot {
+    pub index: Vec<String>,
+    pub on: String,
+    pub values: String,
+    pub aggfunc: PivotFunc,
+    pub error: Option<String>,
+}
+
+impl Default for DataFramePivot {
+    fn default() -> Self {
+        Self {
+            index: Vec::new(),
+            on: String::default(),
+            values: String::default(),
+            aggfunc: PivotFunc::First,
+            error: None,
+        }
+    }
+}
+
+impl DataFramePivot {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.index.is_empty() || self.on.is_empty() || self.values.is_empty() {
+            polars_bail!(ComputeError: "pick the index columns, the column to pivot on and the values");
+        }
+        if self.index.contains(&self.on) || self.index.contains(&self.values) {
+            polars_bail!(ComputeError: "the index columns can't be pivoted on or be the values");
+        }
+        // rows in the order their index first appears.
+        pivot_stable(
+            &df.collect()?,
+            [self.on.as_str()],
+            Some(&self.index),
+            Some([self.values.as_str()]),
+            true,
+            Some(self.aggfunc.apply(col(&self.values))),
+            None,
+        )
+    }
+}
+
+/// Rows become columns and columns rows, the column names going to a `names` column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameTranspose {
+    /// Name the new columns after the values of a column instead of `column_0`, `column_1`...
+    pub use_header: bool,
+    pub header: String,
+    pub names: String,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameTranspose {
+    fn default() -> Self {
+        Self {
+            use_header: false,
+            header: String::default(),
+            names: String::from("column"),
+            error: None,
+        }
+    }
+}
+
+impl DataFrameTranspose {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        let mut df = df.collect()?;
+        let header = match self.use_header {
+            true => {
+                let header = df.drop_in_place(&self.header)?.cast(&DataType::String)?;
+                if header.null_count() > 0 {
+                    polars_bail!(ComputeError: "the header column '{}' has nulls", self.header);
+                }
+                Some(header)
+            }
+            false => None,
+        };
+        let names = Some(self.names.as_str()).filter(|n| !n.trim().is_empty());
+        let mut transposed = df.transpose(names, None)?;
+        if let Some(header) = header {
+            let mut columns: Vec<String> = names.into_iter().map(String::from).collect();
+            columns.extend(header.str()?.into_no_null_iter().map(String::from));
+            transposed.set_column_names(&columns)?;
+        }
+        Ok(transposed)
+    }
+}
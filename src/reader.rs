@@ -0,0 +1,598 @@
+#[cfg(feature = "cloud")]
+use polars::io::cloud::CloudOptions;
+use polars::prelude::*;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum FileFormat {
+    Csv,
+    Parquet,
+    Json,
+    NdJson,
+    Ipc,
+}
+
+impl FileFormat {
+    /// Guesses the format from the file extension, unknown extensions are read as CSV. A
+    /// gzip/zstd compressed file has the format of the extension before the compression one,
+    /// e.g. `sales.csv.gz`.
+    pub fn from_path(path: &Path) -> Self {
+        let mut format = extension(path);
+        if is_compressed(path) {
+            format = path
+                .file_stem()
+                .map(|stem| extension(Path::new(stem)))
+                .unwrap_or_default();
+        }
+        match format.as_str() {
+            "parquet" | "pq" => FileFormat::Parquet,
+            "json" => FileFormat::Json,
+            "ndjson" | "jsonl" => FileFormat::NdJson,
+            "arrow" | "ipc" | "feather" => FileFormat::Ipc,
+            _ => FileFormat::Csv,
+        }
+    }
+
+    /// The format of a file that can be read. Only the CSV reader decompresses files
+    /// transparently, the other formats can't be compressed.
+    pub fn readable(path: &Path) -> PolarsResult<Self> {
+        let format = Self::from_path(path);
+        if format != FileFormat::Csv && is_compressed(path) {
+            polars_bail!(
+                ComputeError: "only CSV files can be compressed, decompress '{}' to read it", path.display()
+            );
+        }
+        Ok(format)
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Whether the file is gzip/zstd compressed.
+fn is_compressed(path: &Path) -> bool {
+    matches!(extension(path).as_str(), "gz" | "zst" | "zstd")
+}
+
+/// File dialog filters for every supported format.
+pub const FILE_FILTERS: [(&str, &[&str]); 6] = [
+    (
+        "All supported",
+        &[
+            "csv", "txt", "gz", "zst", "zstd", "parquet", "pq", "json", "ndjson", "jsonl", "arrow",
+            "ipc", "feather",
+        ],
+    ),
+    ("CSV", &["csv", "txt", "gz", "zst", "zstd"]),
+    ("Parquet", &["parquet", "pq"]),
+    ("JSON", &["json"]),
+    ("NDJSON", &["ndjson", "jsonl"]),
+    ("Arrow IPC / Feather", &["arrow", "ipc", "feather"]),
+];
+
+/// Where the contents of an imported file come from. Native builds read from disk, the web
+/// build only has the bytes handed over by the browser.
+#[derive(Clone, Debug)]
+pub enum ImportSource {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct CsvImportOptions {
+    pub separator: String,
+    pub has_header: bool,
+    pub quote_char: String,
+    pub null_values: String,
+    pub skip_rows: usize,
+    pub lossy_utf8: bool,
+    /// Columns read with a forced type instead of the inferred one.
+    #[serde(default)]
+    pub dtypes: Vec<(String, ColumnType)>,
+}
+
+impl Default for CsvImportOptions {
+    fn default() -> Self {
+        Self {
+            separator: String::from(","),
+            has_header: true,
+            quote_char: String::from("\""),
+            null_values: String::default(),
+            skip_rows: 0,
+            lossy_utf8: false,
+            dtypes: Vec::new(),
+        }
+    }
+}
+
+impl CsvImportOptions {
+    fn encoding(&self) -> CsvEncoding {
+        match self.lossy_utf8 {
+            false => CsvEncoding::Utf8,
+            true => CsvEncoding::LossyUtf8,
+        }
+    }
+
+    fn parsed_null_values(&self) -> Option<NullValues> {
+        let null_values: Vec<String> = self
+            .null_values
+            .split(',')
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .collect();
+        (!null_values.is_empty()).then_some(NullValues::AllColumns(null_values))
+    }
+
+    fn schema_overwrite(&self) -> Option<SchemaRef> {
+        if self.dtypes.is_empty() {
+            return None;
+        }
+        let schema = self
+            .dtypes
+            .iter()
+            .map(|(name, column_type)| Field::new(name, column_type.dtype()))
+            .collect::<Schema>();
+        Some(Arc::new(schema))
+    }
+
+    fn read_options(&self) -> CsvReadOptions {
+        let separator = parse_char(&self.separator).unwrap_or(b',');
+        let quote_char = parse_char(&self.quote_char);
+        let encoding = self.encoding();
+        let null_values = self.parsed_null_values();
+        CsvReadOptions::default()
+            .with_has_header(self.has_header)
+            .with_infer_schema_length(Some(10000))
+            .with_skip_rows(self.skip_rows)
+            .with_schema_overwrite(self.schema_overwrite())
+            .map_parse_options(|parse_options| {
+                parse_options
+                    .with_separator(separator)
+                    .with_quote_char(quote_char)
+                    .with_encoding(encoding)
+                    .with_null_values(null_values.clone())
+            })
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn scan(&self, path: &Path) -> LazyCsvReader {
+        LazyCsvReader::new(path)
+            .with_has_header(self.has_header)
+            .with_infer_schema_length(Some(10000))
+            .with_skip_rows(self.skip_rows)
+            .with_separator(parse_char(&self.separator).unwrap_or(b','))
+            .with_quote_char(parse_char(&self.quote_char))
+            .with_encoding(self.encoding())
+            .with_null_values(self.parsed_null_values())
+            .with_dtype_overwrite(self.schema_overwrite())
+    }
+}
+
+/// Types that can be forced on a CSV column when inference gets it wrong, e.g. zip codes
+/// or ids with leading zeros read as integers.
+#[derive(Clone, Copy, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub enum ColumnType {
+    String,
+    Int64,
+    Float64,
+    Boolean,
+    Date,
+    Datetime,
+}
+
+impl ColumnType {
+    pub const ALL: [ColumnType; 6] = [
+        ColumnType::String,
+        ColumnType::Int64,
+        ColumnType::Float64,
+        ColumnType::Boolean,
+        ColumnType::Date,
+        ColumnType::Datetime,
+    ];
+
+    pub fn dtype(&self) -> DataType {
+        match self {
+            ColumnType::String => DataType::String,
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::Date => DataType::Date,
+            ColumnType::Datetime => DataType::Datetime(TimeUnit::Microseconds, None),
+        }
+    }
+}
+
+/// Single byte separators and quote chars typed in the UI. `\t` is accepted for tabs.
+pub fn parse_char(value: &str) -> Option<u8> {
+    match value {
+        "\\t" => Some(b'\t'),
+        _ => value.bytes().next(),
+    }
+}
+
+/// Reads a CSV file with the given options. `n_rows` limits the rows read, for previews.
+pub fn read_csv(
+    source: &ImportSource,
+    options: &CsvImportOptions,
+    n_rows: Option<usize>,
+) -> PolarsResult<DataFrame> {
+    let read_options = options.read_options().with_n_rows(n_rows);
+    match source {
+        ImportSource::Path(path) => read_options
+            .try_into_reader_with_file_path(Some(path.clone()))?
+            .finish(),
+        ImportSource::Bytes(bytes) => read_options
+            .into_reader_with_file_handle(Cursor::new(bytes.as_slice()))
+            .finish(),
+    }
+}
+
+/// Scans a CSV or Parquet file without reading it into memory. Other formats are read
+/// eagerly.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_file(path: &Path, csv_options: &CsvImportOptions) -> PolarsResult<LazyFrame> {
+    match FileFormat::readable(path)? {
+        FileFormat::Csv => csv_options.scan(path).finish(),
+        FileFormat::Parquet => LazyFrame::scan_parquet(path, ScanArgsParquet::default()),
+        _ => Ok(read_file(path)?.lazy()),
+    }
+}
+
+/// A LazyFrame kept by containers in lazy mode. Wrapped since LazyFrame isn't Debug.
+#[derive(Clone)]
+pub struct LazySource(pub LazyFrame);
+
+impl std::fmt::Debug for LazySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LazySource")
+    }
+}
+
+/// The file a container was read from, so it can be read again when it changes.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileSource {
+    pub path: PathBuf,
+    pub csv_options: CsvImportOptions,
+    /// Only the first rows of a CSV file are read, to inspect the structure of huge files.
+    pub n_rows: Option<usize>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileSource {
+    /// Reads the file, or only scans it if `lazy` and the format supports it.
+    pub fn load(&self, lazy: bool) -> PolarsResult<LoadedData> {
+        if !lazy || !self.scannable() {
+            return self.read().map(LoadedData::Eager);
+        }
+        let lf = self.scan()?;
+        let preview = lf.clone().limit(LAZY_PREVIEW_ROWS).collect()?;
+        let height = lf.clone().select([len()]).collect()?;
+        let height = height.get_columns()[0]
+            .cast(&DataType::UInt64)?
+            .u64()?
+            .get(0)
+            .unwrap_or_default() as usize;
+        Ok(LoadedData::Lazy {
+            lf: Box::new(lf),
+            preview,
+            height,
+        })
+    }
+
+    pub fn read(&self) -> PolarsResult<DataFrame> {
+        match FileFormat::from_path(&self.path) {
+            FileFormat::Csv => read_csv(
+                &ImportSource::Path(self.path.clone()),
+                &self.csv_options,
+                self.n_rows,
+            ),
+            _ => read_file(&self.path),
+        }
+    }
+
+    /// Whether the file can be scanned lazily instead of read. Compressed CSV files have to
+    /// be read, the CSV scan doesn't decompress.
+    fn scannable(&self) -> bool {
+        !is_compressed(&self.path)
+            && matches!(
+                FileFormat::from_path(&self.path),
+                FileFormat::Csv | FileFormat::Parquet
+            )
+    }
+
+    fn scan(&self) -> PolarsResult<LazyFrame> {
+        let lf = scan_file(&self.path, &self.csv_options)?;
+        Ok(match self.n_rows {
+            Some(n_rows) => lf.limit(n_rows as IdxSize),
+            None => lf,
+        })
+    }
+}
+
+/// Number of rows collected for display when a file is scanned lazily.
+#[cfg(not(target_arch = "wasm32"))]
+const LAZY_PREVIEW_ROWS: IdxSize = 1000;
+
+/// The contents of a FileSource: either the whole DataFrame, or a LazyFrame over the file with
+/// its first rows and total height.
+#[cfg(not(target_arch = "wasm32"))]
+pub enum LoadedData {
+    Eager(DataFrame),
+    Lazy {
+        lf: Box<LazyFrame>,
+        preview: DataFrame,
+        height: usize,
+    },
+}
+
+/// A previously opened file, persisted with the app state. CSV files remember the options
+/// they were imported with.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RecentFile {
+    pub path: PathBuf,
+    pub title: String,
+    pub csv_options: Option<CsvImportOptions>,
+}
+
+/// Reads a file into a DataFrame, dispatching to the reader matching its extension.
+pub fn read_file(path: &Path) -> PolarsResult<DataFrame> {
+    match FileFormat::readable(path)? {
+        FileFormat::Csv => read_csv(
+            &ImportSource::Path(path.to_path_buf()),
+            &CsvImportOptions::default(),
+            None,
+        ),
+        FileFormat::Parquet => ParquetReader::new(File::open(path)?).finish(),
+        FileFormat::Json => JsonReader::new(File::open(path)?).finish(),
+        FileFormat::NdJson => JsonLineReader::new(File::open(path)?).finish(),
+        FileFormat::Ipc => IpcReader::new(File::open(path)?).finish(),
+    }
+}
+
+/// Lists the CSV and Parquet files in a folder whose path relative to the folder matches
+/// the glob pattern.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn list_folder(folder: &Path, pattern: &str, recursive: bool) -> Result<Vec<PathBuf>, String> {
+    let pattern = glob::Pattern::new(pattern).map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    let mut folders = vec![folder.to_path_buf()];
+    while let Some(current) = folders.pop() {
+        for entry in std::fs::read_dir(&current).map_err(|e| e.to_string())? {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.is_dir() {
+                if recursive {
+                    folders.push(path);
+                }
+                continue;
+            }
+            let supported = matches!(
+                FileFormat::from_path(&path),
+                FileFormat::Csv | FileFormat::Parquet
+            );
+            let relative = path.strip_prefix(folder).unwrap_or(&path);
+            if supported && pattern.matches_path(relative) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Stacks frames sharing the same schema on top of each other. Returns one frame per
+/// distinct schema, in the order the schemas were first seen.
+pub fn concat_by_schema(frames: Vec<DataFrame>) -> PolarsResult<Vec<DataFrame>> {
+    let mut groups: Vec<DataFrame> = Vec::new();
+    for df in frames {
+        match groups.iter_mut().find(|g| g.schema() == df.schema()) {
+            Some(group) => {
+                group.vstack_mut(&df)?;
+            }
+            None => groups.push(df),
+        }
+    }
+    for group in groups.iter_mut() {
+        group.align_chunks();
+    }
+    Ok(groups)
+}
+
+/// Reads the files of a folder, titled by their path relative to the folder. Concatenated
+/// frames are titled by the folder name instead, numbered when there are several schemas.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_folder(
+    folder: &Path,
+    name: &str,
+    sources: &[FileSource],
+    concatenate: bool,
+) -> PolarsResult<Vec<(String, DataFrame)>> {
+    let frames = sources
+        .iter()
+        .map(|source| {
+            let title = source.path.strip_prefix(folder).unwrap_or(&source.path);
+            Ok((title.display().to_string(), source.read()?))
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+    if !concatenate {
+        return Ok(frames);
+    }
+    let groups = concat_by_schema(frames.into_iter().map(|(_, df)| df).collect())?;
+    Ok(groups
+        .into_iter()
+        .enumerate()
+        .map(|(i, df)| match i {
+            0 => (name.to_string(), df),
+            _ => (format!("{}_{}", name, i), df),
+        })
+        .collect())
+}
+
+/// Reads in-memory file contents (a download or a browser upload) in the given format.
+pub fn read_bytes(bytes: Vec<u8>, format: &FileFormat) -> PolarsResult<DataFrame> {
+    match format {
+        FileFormat::Csv => read_csv(
+            &ImportSource::Bytes(bytes),
+            &CsvImportOptions::default(),
+            None,
+        ),
+        FileFormat::Parquet => ParquetReader::new(Cursor::new(bytes)).finish(),
+        FileFormat::Json => JsonReader::new(Cursor::new(bytes)).finish(),
+        FileFormat::NdJson => JsonLineReader::new(Cursor::new(bytes)).finish(),
+        FileFormat::Ipc => IpcReader::new(Cursor::new(bytes)).finish(),
+    }
+}
+
+/// Credentials typed in the cloud import dialog. Empty fields are left to the environment
+/// (e.g. `AWS_ACCESS_KEY_ID` or `GOOGLE_APPLICATION_CREDENTIALS`).
+#[cfg(feature = "cloud")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CloudCredentials {
+    pub aws_access_key_id: String,
+    pub aws_secret_access_key: String,
+    pub aws_region: String,
+    pub aws_endpoint: String,
+    pub google_service_account: String,
+}
+
+#[cfg(feature = "cloud")]
+impl CloudCredentials {
+    fn cloud_options(&self, url: &str) -> PolarsResult<CloudOptions> {
+        let config = [
+            ("aws_access_key_id", &self.aws_access_key_id),
+            ("aws_secret_access_key", &self.aws_secret_access_key),
+            ("aws_region", &self.aws_region),
+            ("aws_endpoint", &self.aws_endpoint),
+            ("google_service_account", &self.google_service_account),
+        ];
+        let prefix = if url.starts_with("s3://") {
+            "aws"
+        } else {
+            "google"
+        };
+        CloudOptions::from_untyped_config(
+            url,
+            config
+                .into_iter()
+                .filter(|(key, value)| key.starts_with(prefix) && !value.is_empty())
+                .map(|(key, value)| (key, value.clone())),
+        )
+    }
+}
+
+/// Scans a Parquet or CSV file from object storage (`s3://` or `gs://` paths).
+#[cfg(feature = "cloud")]
+pub fn read_cloud(url: &str, credentials: &CloudCredentials) -> PolarsResult<DataFrame> {
+    let cloud_options = Some(credentials.cloud_options(url)?);
+    match FileFormat::readable(Path::new(url))? {
+        FileFormat::Parquet => LazyFrame::scan_parquet(
+            url,
+            ScanArgsParquet {
+                cloud_options,
+                ..Default::default()
+            },
+        )?
+        .collect(),
+        FileFormat::Csv => LazyCsvReader::new(url)
+            .with_has_header(true)
+            .with_infer_schema_length(Some(10000))
+            .with_cloud_options(cloud_options)
+            .finish()?
+            .collect(),
+        _ => {
+            polars_bail!(ComputeError: "only Parquet and CSV files can be read from object storage")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(path: &str) -> FileFormat {
+        FileFormat::from_path(Path::new(path))
+    }
+
+    #[test]
+    fn format_from_the_extension() {
+        assert_eq!(format("sales.csv"), FileFormat::Csv);
+        assert_eq!(format("sales.tsv"), FileFormat::Csv);
+        assert_eq!(format("sales"), FileFormat::Csv);
+        assert_eq!(format("dir/sales.PARQUET"), FileFormat::Parquet);
+        assert_eq!(format("sales.pq"), FileFormat::Parquet);
+        assert_eq!(format("sales.json"), FileFormat::Json);
+        assert_eq!(format("sales.jsonl"), FileFormat::NdJson);
+        assert_eq!(format("sales.feather"), FileFormat::Ipc);
+    }
+
+    #[test]
+    fn compressed_files_have_the_format_before_the_suffix() {
+        assert_eq!(format("sales.csv.gz"), FileFormat::Csv);
+        assert_eq!(format("sales.gz"), FileFormat::Csv);
+        assert_eq!(format("sales.ndjson.gz"), FileFormat::NdJson);
+        assert_eq!(format("sales.json.zst"), FileFormat::Json);
+        assert_eq!(format("sales.parquet.ZSTD"), FileFormat::Parquet);
+    }
+
+    #[test]
+    fn only_csv_files_can_be_compressed() {
+        let readable = |path: &str| FileFormat::readable(Path::new(path));
+        assert_eq!(readable("sales.csv.gz").unwrap(), FileFormat::Csv);
+        assert_eq!(readable("sales.gz").unwrap(), FileFormat::Csv);
+        assert_eq!(readable("sales.json").unwrap(), FileFormat::Json);
+        let error = readable("sales.json.gz").unwrap_err().to_string();
+        assert!(
+            error.contains("only CSV files can be compressed"),
+            "{}",
+            error
+        );
+        assert!(readable("sales.parquet.zst").is_err());
+        // read_file doesn't reach the JSON reader.
+        let error = read_file(Path::new("missing.json.gz"))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("only CSV files can be compressed"),
+            "{}",
+            error
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn folder_csv_files_are_read_with_the_options() {
+        let folder = std::env::temp_dir().join(format!("polarsgui-folder-{}", std::process::id()));
+        std::fs::create_dir_all(folder.join("2024")).unwrap();
+        std::fs::write(folder.join("a.csv"), "x;y\n1;2\n").unwrap();
+        std::fs::write(folder.join("2024/b.csv"), "x;y\n3;4\n").unwrap();
+        let options = CsvImportOptions {
+            separator: String::from(";"),
+            ..Default::default()
+        };
+        let sources: Vec<FileSource> = list_folder(&folder, "**/*.csv", true)
+            .unwrap()
+            .into_iter()
+            .map(|path| FileSource {
+                path,
+                csv_options: options.clone(),
+                n_rows: None,
+            })
+            .collect();
+        let frames = read_folder(&folder, "sales", &sources, false).unwrap();
+        let concatenated = read_folder(&folder, "sales", &sources, true).unwrap();
+        std::fs::remove_dir_all(&folder).unwrap();
+
+        let titles: Vec<&str> = frames.iter().map(|(title, _)| title.as_str()).collect();
+        assert_eq!(titles, ["2024/b.csv", "a.csv"]);
+        assert_eq!(frames[0].1.get_column_names(), ["x", "y"]);
+        assert_eq!(concatenated.len(), 1);
+        assert_eq!(concatenated[0].0, "sales");
+        assert_eq!(concatenated[0].1.height(), 2);
+    }
+}
@@ -0,0 +1,152 @@
+use egui::{Color32, ComboBox, Grid, RichText, TextEdit};
+use polars::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RuleCondition {
+    GreaterThan,
+    LowerThan,
+    Equal,
+    Contains,
+    IsNull,
+}
+
+impl RuleCondition {
+    pub const ALL: [RuleCondition; 5] = [
+        RuleCondition::GreaterThan,
+        RuleCondition::LowerThan,
+        RuleCondition::Equal,
+        RuleCondition::Contains,
+        RuleCondition::IsNull,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RuleCondition::GreaterThan => ">",
+            RuleCondition::LowerThan => "<",
+            RuleCondition::Equal => "=",
+            RuleCondition::Contains => "contains",
+            RuleCondition::IsNull => "is null",
+        }
+    }
+}
+
+/// A conditional formatting rule of the data view, e.g. values of "price" above 100 with a
+/// red background.
+#[derive(Clone, Debug)]
+pub struct FormatRule {
+    /// Column the rule applies to, all columns when empty.
+    pub column: String,
+    pub condition: RuleCondition,
+    pub value: String,
+    pub background: Option<Color32>,
+    pub bold: bool,
+}
+
+impl Default for FormatRule {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            condition: RuleCondition::GreaterThan,
+            value: String::new(),
+            background: Some(Color32::from_rgba_unmultiplied(220, 50, 50, 100)),
+            bold: false,
+        }
+    }
+}
+
+impl FormatRule {
+    /// Whether the rule applies to a value of the given column. Comparisons are numeric when
+    /// both sides are numbers and textual otherwise.
+    pub fn matches(&self, column: &str, value: &AnyValue<'_>) -> bool {
+        if !self.column.is_empty() && self.column != column {
+            return false;
+        }
+        let text = match value {
+            AnyValue::Null => return self.condition == RuleCondition::IsNull,
+            AnyValue::String(v) => v.to_string(),
+            v => v.to_string(),
+        };
+        let numbers = match (value.dtype().is_numeric(), self.value.trim().parse::<f64>()) {
+            (true, Ok(rule)) => value.extract::<f64>().map(|v| (v, rule)),
+            _ => None,
+        };
+        match (&self.condition, numbers) {
+            (RuleCondition::GreaterThan, Some((v, rule))) => v > rule,
+            (RuleCondition::GreaterThan, None) => text.as_str() > self.value.as_str(),
+            (RuleCondition::LowerThan, Some((v, rule))) => v < rule,
+            (RuleCondition::LowerThan, None) => text.as_str() < self.value.as_str(),
+            (RuleCondition::Equal, Some((v, rule))) => v == rule,
+            (RuleCondition::Equal, None) => text == self.value,
+            (RuleCondition::Contains, _) => text.contains(&self.value),
+            (RuleCondition::IsNull, _) => false,
+        }
+    }
+
+    /// Applies the rule style to a cell: the background is painted on the cell and the text
+    /// style returned.
+    pub fn apply(&self, text: RichText, ui: &mut egui::Ui) -> RichText {
+        if let Some(color) = self.background {
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+        match self.bold {
+            true => text.strong(),
+            false => text,
+        }
+    }
+}
+
+/// Editor with a row per rule. Rules are applied in order, all the matching ones styling
+/// the cell.
+pub fn show_rules(rules: &mut Vec<FormatRule>, columns: &[&str], ui: &mut egui::Ui) {
+    let mut remove = None;
+    Grid::new("format_rules").striped(true).show(ui, |ui| {
+        for (i, rule) in rules.iter_mut().enumerate() {
+            let column = match rule.column.is_empty() {
+                true => "All columns",
+                false => rule.column.as_str(),
+            };
+            ComboBox::from_id_source(("rule_column", i))
+                .selected_text(column.to_string())
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut rule.column, String::new(), "All columns");
+                    for column in columns {
+                        ui.selectable_value(&mut rule.column, column.to_string(), *column);
+                    }
+                });
+            ComboBox::from_id_source(("rule_condition", i))
+                .selected_text(rule.condition.label())
+                .show_ui(ui, |ui| {
+                    for condition in RuleCondition::ALL {
+                        ui.selectable_value(&mut rule.condition, condition, condition.label());
+                    }
+                });
+            ui.add_enabled(
+                rule.condition != RuleCondition::IsNull,
+                TextEdit::singleline(&mut rule.value).desired_width(100.0),
+            );
+            ui.horizontal(|ui| {
+                let mut filled = rule.background.is_some();
+                if ui.checkbox(&mut filled, "Background").changed() {
+                    rule.background = match filled {
+                        true => FormatRule::default().background,
+                        false => None,
+                    };
+                }
+                if let Some(color) = &mut rule.background {
+                    ui.color_edit_button_srgba(color);
+                }
+            });
+            ui.checkbox(&mut rule.bold, "Bold");
+            if ui.button("✖").clicked() {
+                remove = Some(i);
+            }
+            ui.end_row();
+        }
+    });
+    if let Some(i) = remove {
+        rules.remove(i);
+    }
+    if ui.button("Add rule").clicked() {
+        rules.push(FormatRule::default());
+    }
+}
@@ -0,0 +1,205 @@
+use egui::{DragValue, Grid, Window};
+use polars::prelude::*;
+
+const CATEGORIES: [&str; 5] = ["Books", "Electronics", "Garden", "Grocery", "Toys"];
+const REGIONS: [&str; 5] = ["North", "South", "North", "East", "West"];
+/// 2023-01-01 as days since the unix epoch.
+const START_DATE: i32 = 19358;
+
+/// Dialog generating synthetic frames to try the transformations without a file at hand: a
+/// "sales" frame and, optionally, a "categories" lookup frame it can be joined with.
+#[derive(Clone, Debug)]
+pub struct SampleDataDialog {
+    pub rows: usize,
+    pub seed: u64,
+    pub lookup: bool,
+    pub is_open: bool,
+    pub create: bool,
+}
+
+impl Default for SampleDataDialog {
+    fn default() -> Self {
+        Self {
+            rows: 1000,
+            seed: 42,
+            lookup: true,
+            is_open: true,
+            create: false,
+        }
+    }
+}
+
+impl SampleDataDialog {
+    /// The generated frames with their titles.
+    pub fn generate(&self) -> PolarsResult<Vec<(String, DataFrame)>> {
+        let mut rng = SplitMix64(self.seed);
+        let mut days = Vec::with_capacity(self.rows);
+        let mut categories = Vec::with_capacity(self.rows);
+        let mut quantities = Vec::with_capacity(self.rows);
+        let mut prices = Vec::with_capacity(self.rows);
+        let mut discounts = Vec::with_capacity(self.rows);
+        let mut returned = Vec::with_capacity(self.rows);
+        for _ in 0..self.rows {
+            days.push(START_DATE + rng.below(365) as i32);
+            categories.push(CATEGORIES[rng.below(CATEGORIES.len() as u64) as usize]);
+            quantities.push(1 + rng.below(10) as i64);
+            // normally distributed prices, rounded to cents and never below 1.
+            prices.push(((50.0 + 15.0 * rng.normal()).max(1.0) * 100.0).round() / 100.0);
+            // a fifth of the sales have no discount recorded, to try null handling.
+            discounts.push((rng.uniform() > 0.2).then(|| (rng.uniform() * 30.0).round()));
+            returned.push(rng.uniform() < 0.05);
+        }
+        let sales = df!(
+            "id" => (1..=self.rows as i64).collect::<Vec<i64>>(),
+            "date" => Series::new("date", days).cast(&DataType::Date)?,
+            "category" => categories,
+            "quantity" => quantities,
+            "price" => prices,
+            "discount" => discounts,
+            "returned" => returned,
+        )?;
+        let mut frames = vec![(String::from("sample_sales"), sales)];
+        if self.lookup {
+            let lookup = df!(
+                "category" => CATEGORIES,
+                "region" => REGIONS,
+            )?;
+            frames.push((String::from("sample_categories"), lookup));
+        }
+        Ok(frames)
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut is_open = std::mem::take(&mut self.is_open);
+        Window::new("Sample Data")
+            .open(&mut is_open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                Grid::new("sample_data_grid")
+                    .num_columns(2)
+                    .spacing([40.0, 4.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Rows:");
+                        ui.add(DragValue::new(&mut self.rows).range(1..=10_000_000));
+                        ui.end_row();
+                        ui.label("Seed:");
+                        ui.add(DragValue::new(&mut self.seed));
+                        ui.end_row();
+                        ui.label("Lookup frame:");
+                        ui.checkbox(&mut self.lookup, "Categories to join with");
+                        ui.end_row();
+                    });
+                ui.add_space(10.0);
+                if ui.button("Create").clicked() {
+                    self.create = true;
+                }
+            });
+        self.is_open = is_open;
+    }
+}
+
+/// `n` distinct rows picked at random, in their original order.
+pub fn sample_rows(df: &DataFrame, n: usize, seed: u64) -> PolarsResult<DataFrame> {
+    let mut rng = SplitMix64(seed);
+    let mut rows: Vec<IdxSize> = (0..df.height() as IdxSize).collect();
+    let n = n.min(rows.len());
+    // partial Fisher-Yates shuffle, the first n rows end up sampled.
+    for i in 0..n {
+        let j = i + rng.below((rows.len() - i) as u64) as usize;
+        rows.swap(i, j);
+    }
+    rows.truncate(n);
+    rows.sort_unstable();
+    df.take(&IdxCa::from_vec("rows", rows))
+}
+
+/// `n` rows drawn at random, each row possibly more than once, in their original order.
+pub fn sample_rows_with_replacement(
+    df: &DataFrame,
+    n: usize,
+    seed: u64,
+) -> PolarsResult<DataFrame> {
+    if df.height() == 0 {
+        return Ok(df.clear());
+    }
+    let mut rng = SplitMix64(seed);
+    let mut rows: Vec<IdxSize> = (0..n)
+        .map(|_| rng.below(df.height() as u64) as IdxSize)
+        .collect();
+    rows.sort_unstable();
+    df.take(&IdxCa::from_vec("rows", rows))
+}
+
+/// A random subset of the rows, as a number of rows or a fraction of them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameSample {
+    pub by_fraction: bool,
+    pub n: usize,
+    pub fraction: f64,
+    pub replacement: bool,
+    /// Seed for a repeatable sample, a new sample each time if unset.
+    pub seed: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameSample {
+    fn default() -> Self {
+        Self {
+            by_fraction: false,
+            n: 1000,
+            fraction: 0.1,
+            replacement: false,
+            seed: None,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameSample {
+    /// `seed` is used when no seed is set.
+    pub fn apply(&self, df: LazyFrame, seed: u64) -> PolarsResult<DataFrame> {
+        let df = df.collect()?;
+        let n = match self.by_fraction {
+            true => (df.height() as f64 * self.fraction).round() as usize,
+            false => self.n,
+        };
+        let seed = self.seed.unwrap_or(seed);
+        match self.replacement {
+            true => sample_rows_with_replacement(&df, n, seed),
+            false if n > df.height() => polars_bail!(
+                ComputeError: "cannot sample {} of {} rows without replacement", n, df.height()
+            ),
+            false => sample_rows(&df, n, seed),
+        }
+    }
+}
+
+/// Small seedable generator, good enough for sample data.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in [0, 1).
+    fn uniform(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    /// Standard normal, with the Box-Muller transform.
+    fn normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.uniform();
+        let u2 = self.uniform();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+}
@@ -0,0 +1,42 @@
+use polars::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SliceMode {
+    Head,
+    Tail,
+    Window,
+}
+
+/// Keeps the first or last rows, or a window of rows starting at an offset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameSlice {
+    pub mode: SliceMode,
+    /// Rows kept by Head and Tail.
+    pub n: usize,
+    /// First row of the window, counting back from the end if negative.
+    pub offset: i64,
+    pub length: usize,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameSlice {
+    fn default() -> Self {
+        Self {
+            mode: SliceMode::Head,
+            n: 100,
+            offset: 0,
+            length: 100,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameSlice {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        match self.mode {
+            SliceMode::Head => df.limit(self.n as IdxSize).collect(),
+            SliceMode::Tail => df.tail(self.n as IdxSize).collect(),
+            SliceMode::Window => df.slice(self.offset, self.length as IdxSize).collect(),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use polars::prelude::*;
+
+/// A sort key: the column and its direction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SortKey {
+    pub column: String,
+    pub descending: bool,
+    pub nulls_last: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct DataFrameSort {
+    pub selection: String,
+    pub keys: Vec<SortKey>,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl DataFrameSort {
+    /// Sorts by the keys in order, later keys breaking the ties of the earlier ones.
+    pub fn sort(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.keys.is_empty() {
+            polars_bail!(ComputeError: "add a column to sort by");
+        }
+        let by: Vec<Expr> = self.keys.iter().map(|k| col(&k.column)).collect();
+        let options = SortMultipleOptions::default()
+            .with_order_descending_multi(self.keys.iter().map(|k| k.descending))
+            .with_nulls_last_multi(self.keys.iter().map(|k| k.nulls_last))
+            .with_maintain_order(true);
+        df.sort_by_exprs(by, options).collect()
+    }
+}
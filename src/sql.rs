@@ -0,0 +1,75 @@
+use egui::{TextEdit, Window};
+use polars::prelude::*;
+use polars::sql::SQLContext;
+
+/// Window running SQL queries over the loaded containers, registered as tables by title.
+#[derive(Clone, Debug)]
+pub struct SqlDialog {
+    pub query: String,
+    /// Titles of the containers that can be queried.
+    pub tables: Vec<String>,
+    pub error: Option<String>,
+    pub is_open: bool,
+    pub run: bool,
+}
+
+impl Default for SqlDialog {
+    fn default() -> Self {
+        Self {
+            query: String::default(),
+            tables: Vec::new(),
+            error: None,
+            is_open: true,
+            run: false,
+        }
+    }
+}
+
+impl SqlDialog {
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let mut is_open = std::mem::take(&mut self.is_open);
+        Window::new("SQL")
+            .open(&mut is_open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Tables:");
+                    if self.tables.is_empty() {
+                        ui.label("none loaded");
+                    }
+                    for table in &self.tables {
+                        // titles like "data.csv" need quoting, a click inserts the quoted name.
+                        if ui.button(table).clicked() {
+                            self.query.push_str(&format!("\"{}\"", table));
+                        }
+                    }
+                });
+                ui.add(
+                    TextEdit::multiline(&mut self.query)
+                        .code_editor()
+                        .hint_text("SELECT * FROM \"data.csv\" LIMIT 10")
+                        .desired_rows(8)
+                        .desired_width(400.0),
+                );
+                if ui
+                    .add_enabled(!self.query.trim().is_empty(), egui::Button::new("Run"))
+                    .clicked()
+                {
+                    self.run = true;
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+        self.is_open = is_open;
+    }
+}
+
+/// Runs a query with each frame registered as a table under its title.
+pub fn run_query(tables: Vec<(String, LazyFrame)>, query: &str) -> PolarsResult<DataFrame> {
+    let mut context = SQLContext::new();
+    for (title, lf) in tables {
+        context.register(&title, lf);
+    }
+    context.execute(query)?.collect()
+}
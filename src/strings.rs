@@ -0,0 +1,380 @@
+use polars::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum StringOp {
+    Lowercase,
+    Uppercase,
+    Strip,
+    PadStart,
+    PadEnd,
+    Slice,
+    Length,
+}
+
+impl StringOp {
+    pub const ALL: [StringOp; 7] = [
+        StringOp::Lowercase,
+        StringOp::Uppercase,
+        StringOp::Strip,
+        StringOp::PadStart,
+        StringOp::PadEnd,
+        StringOp::Slice,
+        StringOp::Length,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StringOp::Lowercase => "Lowercase",
+            StringOp::Uppercase => "Uppercase",
+            StringOp::Strip => "Strip whitespace",
+            StringOp::PadStart => "Pad left",
+            StringOp::PadEnd => "Pad right",
+            StringOp::Slice => "Slice",
+            StringOp::Length => "Length",
+        }
+    }
+}
+
+/// An operation on a text column, replacing the column or written to a new one.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameStringOp {
+    pub column: String,
+    pub op: StringOp,
+    /// Width and fill character of the pads.
+    pub width: usize,
+    pub fill: String,
+    /// Start and length of the slice, to the end of the text without a length.
+    pub offset: i64,
+    pub length: Option<usize>,
+    /// Write the result to `name` instead of replacing the column.
+    pub new_column: bool,
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameStringOp {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            op: StringOp::Lowercase,
+            width: 10,
+            fill: String::from(" "),
+            offset: 0,
+            length: None,
+            new_column: false,
+            name: String::new(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameStringOp {
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        let dtype = df.schema()?.try_get(&self.column)?.clone();
+        if dtype != DataType::String {
+            polars_bail!(ComputeError: "'{}' is not a text column", self.column);
+        }
+        let text = col(&self.column).str();
+        let fill = self.fill.chars().next().unwrap_or(' ');
+        let expr = match self.op {
+            StringOp::Lowercase => text.to_lowercase(),
+            StringOp::Uppercase => text.to_uppercase(),
+            StringOp::Strip => text.strip_chars(lit(NULL)),
+            StringOp::PadStart => text.pad_start(self.width, fill),
+            StringOp::PadEnd => text.pad_end(self.width, fill),
+            StringOp::Slice => {
+                let length = match self.length {
+                    Some(length) => lit(length as u64),
+                    None => lit(NULL),
+                };
+                text.slice(lit(self.offset), length)
+            }
+            StringOp::Length => text.len_chars(),
+        };
+        let name = match self.new_column {
+            true if self.name.trim().is_empty() => {
+                polars_bail!(ComputeError: "the new column needs a name")
+            }
+            true => self.name.trim(),
+            false => &self.column,
+        };
+        df.with_column(expr.alias(name)).collect()
+    }
+}
+
+/// Writes a capture group of a regex, matched on a text column, to a new column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameExtract {
+    pub column: String,
+    pub pattern: String,
+    /// Capture group, 0 for the whole match.
+    pub group: usize,
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+    pub preview: Option<ExtractPreview>,
+}
+
+/// Texts and their extracted values for the first rows, or the regex error, with the inputs
+/// they were made for.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExtractPreview {
+    column: String,
+    pattern: String,
+    group: usize,
+    pub rows: Result<Vec<(String, String)>, String>,
+}
+
+impl Default for DataFrameExtract {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            pattern: String::new(),
+            group: 1,
+            name: String::from("extracted"),
+            inplace: false,
+            error: None,
+            preview: None,
+        }
+    }
+}
+
+impl DataFrameExtract {
+    fn expr(&self) -> Expr {
+        col(&self.column)
+            .str()
+            .extract(lit(self.pattern.clone()), self.group)
+    }
+
+    /// Runs the extraction on the first rows whenever the inputs change, which also
+    /// validates the regex as it's typed.
+    pub fn update_preview(&mut self, df: &DataFrame) {
+        if self.column.is_empty() || self.pattern.is_empty() {
+            self.preview = None;
+            return;
+        }
+        if let Some(preview) = &self.preview {
+            if (&preview.column, &preview.pattern, preview.group)
+                == (&self.column, &self.pattern, self.group)
+            {
+                return;
+            }
+        }
+        let text = |v: AnyValue<'_>| match v {
+            AnyValue::String(s) => s.to_string(),
+            v => v.to_string(),
+        };
+        let rows = df
+            .head(Some(5))
+            .lazy()
+            .select([col(&self.column), self.expr().alias("extracted")])
+            .collect()
+            .map(|df| {
+                let columns = df.get_columns();
+                columns[0]
+                    .iter()
+                    .zip(columns[1].iter())
+                    .map(|(v, e)| (text(v), text(e)))
+                    .collect()
+            })
+            .map_err(|e| e.to_string());
+        self.preview = Some(ExtractPreview {
+            column: self.column.clone(),
+            pattern: self.pattern.clone(),
+            group: self.group,
+            rows,
+        });
+    }
+
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.name.trim().is_empty() {
+            polars_bail!(ComputeError: "the new column needs a name");
+        }
+        df.with_column(self.expr().alias(self.name.trim()))
+            .collect()
+    }
+}
+
+/// A text column joining the values of several columns, in the order they were picked, e.g.
+/// street, city and zip code into an address.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameConcatColumns {
+    pub columns: Vec<String>,
+    pub separator: String,
+    /// Skip the null values instead of making the whole result null.
+    pub ignore_nulls: bool,
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameConcatColumns {
+    fn default() -> Self {
+        Self {
+            columns: Vec::new(),
+            separator: String::from(" "),
+            ignore_nulls: true,
+            name: String::from("combined"),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameConcatColumns {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.columns.len() < 2 {
+            polars_bail!(ComputeError: "pick at least two columns to concatenate");
+        }
+        if self.name.trim().is_empty() {
+            polars_bail!(ComputeError: "the new column needs a name");
+        }
+        let exprs: Vec<Expr> = self.columns.iter().map(|c| col(c)).collect();
+        let expr = concat_str(exprs, &self.separator, self.ignore_nulls);
+        df.with_column(expr.alias(self.name.trim())).collect()
+    }
+}
+
+const UNIT_SEPARATOR: &str = "\u{1f}";
+
+/// Splits a text column on a delimiter or the matches of a regex, into `parts` new columns or
+/// a single list column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameSplit {
+    pub column: String,
+    pub delimiter: String,
+    pub regex: bool,
+    pub into_list: bool,
+    /// Number of new columns, later parts are left out.
+    pub parts: usize,
+    /// Strip the whitespace around the parts of new columns.
+    pub strip: bool,
+    /// Name of the list column, or prefix of the new columns, the column name if empty.
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameSplit {
+    fn default() -> Self {
+        Self {
+            column: String::new(),
+            delimiter: String::from(","),
+            regex: false,
+            into_list: false,
+            parts: 2,
+            strip: true,
+            name: String::new(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameSplit {
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        let dtype = df.schema()?.try_get(&self.column)?.clone();
+        if dtype != DataType::String {
+            polars_bail!(ComputeError: "'{}' is not a text column", self.column);
+        }
+        if self.delimiter.is_empty() {
+            polars_bail!(ComputeError: "type the delimiter to split on");
+        }
+        let df = df.collect()?;
+        // there is no split on a regex, the matches are replaced by the unit separator control
+        // character first, which texts that have it already would be split on as well.
+        if self.regex
+            && df
+                .column(&self.column)?
+                .str()?
+                .contains_literal(UNIT_SEPARATOR)?
+                .any()
+        {
+            polars_bail!(
+                ComputeError: "'{}' has texts with the control character U+001F, they can't be split on a regex", self.column
+            );
+        }
+        let text = col(&self.column).str();
+        let list = match self.regex {
+            true => text
+                .replace_all(lit(self.delimiter.clone()), lit(UNIT_SEPARATOR), false)
+                .str()
+                .split(lit(UNIT_SEPARATOR)),
+            false => text.split(lit(self.delimiter.clone())),
+        };
+        let name = match self.name.trim() {
+            "" => self.column.as_str(),
+            name => name,
+        };
+        let exprs = match self.into_list {
+            true => vec![list.alias(name)],
+            false => {
+                if self.parts == 0 {
+                    polars_bail!(ComputeError: "split into at least one column");
+                }
+                (0..self.parts)
+                    .map(|i| {
+                        let part = list.clone().list().get(lit(i as i64), true);
+                        let part = match self.strip {
+                            true => part.str().strip_chars(lit(NULL)),
+                            false => part,
+                        };
+                        part.alias(&format!("{}_{}", name, i + 1))
+                    })
+                    .collect()
+            }
+        };
+        df.lazy().with_columns(exprs).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(values: &[&str], delimiter: &str, regex: bool) -> PolarsResult<DataFrame> {
+        let df = df! { "text" => values }.unwrap();
+        let split = DataFrameSplit {
+            column: String::from("text"),
+            delimiter: String::from(delimiter),
+            regex,
+            ..Default::default()
+        };
+        split.apply(df.lazy())
+    }
+
+    #[test]
+    fn split_into_columns() {
+        let df = split(&["a, b, c", "d"], ",", false).unwrap();
+        assert_eq!(
+            df.column("text_1").unwrap(),
+            &Series::new("text_1", ["a", "d"])
+        );
+        assert_eq!(
+            df.column("text_2").unwrap(),
+            &Series::new("text_2", [Some("b"), None])
+        );
+    }
+
+    #[test]
+    fn split_on_a_regex() {
+        let df = split(&["a1b22c", "d"], "[0-9]+", true).unwrap();
+        assert_eq!(
+            df.column("text_1").unwrap(),
+            &Series::new("text_1", ["a", "d"])
+        );
+        assert_eq!(
+            df.column("text_2").unwrap(),
+            &Series::new("text_2", [Some("b"), None])
+        );
+    }
+
+    #[test]
+    fn regex_split_rejects_the_unit_separator() {
+        let error = split(&["a\u{1f}b", "c"], "x", true).unwrap_err();
+        assert!(error.to_string().contains("U+001F"));
+        assert!(split(&["a\u{1f}b"], "x", false).is_ok());
+    }
+}
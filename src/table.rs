@@ -0,0 +1,1240 @@
+use crate::filter::FilterOps;
+use crate::rules::{show_rules, FormatRule};
+use egui::{Button, Color32, ComboBox, DragValue, RichText, Sense, TextEdit};
+use egui_extras::{Column, TableBuilder};
+use polars::export::arrow::temporal_conversions::{
+    date32_to_datetime_opt, timestamp_ms_to_datetime_opt, timestamp_ns_to_datetime_opt,
+    timestamp_us_to_datetime_opt,
+};
+use polars::prelude::*;
+use std::collections::{BTreeSet, HashMap};
+use std::fmt::Write;
+use std::io::Cursor;
+use std::ops::Range;
+
+const PAGE_SIZES: [usize; 4] = [100, 1_000, 10_000, 100_000];
+
+/// State of a container's data view. Rows are shown one page at a time so only a slice of
+/// large frames is rendered.
+#[derive(Clone, Debug)]
+pub struct TableView {
+    pub page: usize,
+    pub page_size: usize,
+    /// Rows of the (sorted) view that were visible in the last frame.
+    pub visible_rows: Range<usize>,
+    /// Column the view is sorted by, and whether descending. Clicking a header cycles
+    /// through ascending, descending and unsorted.
+    pub sort: Option<(String, bool)>,
+    /// Columns left out of the view. The DataFrame itself keeps them.
+    pub hidden: Vec<String>,
+    pub column_search: String,
+    /// Columns kept on the left while the others scroll horizontally.
+    pub frozen: Vec<String>,
+    /// Page of the columns shown, for frames too wide to draw every column.
+    pub column_page: usize,
+    pub columns_per_page: usize,
+    /// Widths of the columns, by name, as last shown.
+    pub widths: HashMap<String, f32>,
+    /// Selected cells, copied to the clipboard with Ctrl+C.
+    pub selection: Option<CellSelection>,
+    /// Rows ticked in the row number column, as row indices of the DataFrame.
+    pub selected_rows: BTreeSet<usize>,
+    /// How numbers and dates are displayed, unless their column has a format of its own.
+    pub value_format: ValueFormat,
+    pub column_formats: HashMap<String, ValueFormat>,
+    pub highlight_nulls: bool,
+    /// Conditional formatting rules, edited in a window of their own.
+    pub rules: Vec<FormatRule>,
+    pub show_rules: bool,
+    pub search: ViewSearch,
+    /// Row of the view shown in the record inspector, a column per line.
+    pub inspect: Option<usize>,
+    /// Whether double clicking a cell edits it.
+    pub edit_mode: bool,
+    /// Error of the last edit, e.g. a value that doesn't parse to the column type.
+    pub edit_error: Option<String>,
+    editing: Option<CellEdit>,
+    edited: Option<CellEdit>,
+    /// Filter picked from a cell context menu, for the container to run.
+    filter: Option<(String, FilterOps, String)>,
+    /// Whether a selection rectangle is being dragged.
+    dragging: bool,
+    /// Statistics of the columns whose header was hovered, computed once per data change.
+    stats: HashMap<String, ColumnStats>,
+    /// Whether the layout saved for the container title was restored.
+    pub restored: bool,
+    /// Row order for the current sort, computed once per sort and data change.
+    order: Option<IdxCa>,
+}
+
+impl Default for TableView {
+    fn default() -> Self {
+        Self {
+            page: 0,
+            page_size: 1_000,
+            visible_rows: 0..0,
+            sort: None,
+            hidden: Vec::new(),
+            column_search: String::new(),
+            frozen: Vec::new(),
+            column_page: 0,
+            columns_per_page: 25,
+            widths: HashMap::new(),
+            selection: None,
+            selected_rows: BTreeSet::new(),
+            value_format: ValueFormat::default(),
+            column_formats: HashMap::new(),
+            highlight_nulls: true,
+            rules: Vec::new(),
+            show_rules: false,
+            search: ViewSearch::default(),
+            inspect: None,
+            edit_mode: false,
+            edit_error: None,
+            editing: None,
+            edited: None,
+            filter: None,
+            dragging: false,
+            stats: HashMap::new(),
+            restored: false,
+            order: None,
+        }
+    }
+}
+
+impl TableView {
+    pub fn show(&mut self, df: &DataFrame, ui: &mut egui::Ui) {
+        if let Err(e) = self.update_order(df) {
+            log::error!("Could not sort by {:?}: {}", self.sort, e);
+            self.sort = None;
+        }
+        ui.horizontal(|ui| {
+            self.show_pager(df.height(), ui);
+            ui.separator();
+            self.show_column_picker(df, ui);
+            self.show_column_pager(df, ui);
+            ui.menu_button("Format", |ui| {
+                ui.checkbox(&mut self.highlight_nulls, "Highlight nulls");
+                ui.separator();
+                ui.label("Numbers");
+                self.value_format.show_number(ui);
+                ui.separator();
+                ui.label("Dates");
+                self.value_format.show_datetime(ui);
+            });
+            ui.toggle_value(&mut self.show_rules, "Rules");
+            if !self.selected_rows.is_empty() {
+                ui.separator();
+                ui.label(format!("{} rows selected", self.selected_rows.len()));
+                if ui.button("Clear").clicked() {
+                    self.selected_rows.clear();
+                }
+            }
+            ui.separator();
+            ui.checkbox(&mut self.edit_mode, "Edit")
+                .on_hover_text("Double click a cell to edit it");
+            if let Some(error) = &self.edit_error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            }
+        });
+        ui.horizontal(|ui| self.show_search(df, ui));
+        if self.show_rules {
+            let columns = df.get_column_names();
+            egui::Window::new("Formatting rules")
+                .id(ui.id().with("format_rules"))
+                .open(&mut self.show_rules)
+                .show(ui.ctx(), |ui| show_rules(&mut self.rules, &columns, ui));
+        }
+        if let Some(row) = self.inspect {
+            let mut is_open = true;
+            // titled like the row label that was clicked, the row of the data.
+            egui::Window::new(format!("Row {}", self.data_row(row)))
+                .id(ui.id().with("record"))
+                .open(&mut is_open)
+                .show(ui.ctx(), |ui| self.show_record(df, row, ui));
+            if !is_open {
+                self.inspect = None;
+            }
+        }
+        let offset = self.page * self.page_size;
+        let page = self
+            .rows(df, offset..offset + self.page_size)
+            .and_then(|page| page.select(self.shown_columns(&page)));
+        let page = match page {
+            Ok(page) => page,
+            Err(e) => {
+                ui.colored_label(ui.visuals().error_fg_color, e.to_string());
+                return;
+            }
+        };
+        let mut frame = FrameState {
+            editing: self.editing.take(),
+            widths: std::mem::take(&mut self.widths),
+        };
+        let (visible, action) = self.show_rows(&page, offset, &mut frame, ui);
+        self.editing = frame.editing;
+        self.widths = frame.widths;
+        self.search.scroll_to = None;
+        self.visible_rows = visible.start + offset..visible.end + offset;
+        match action {
+            Some(TableAction::Sort(column)) => {
+                self.sort = match self.sort.take() {
+                    Some((sorted, false)) if sorted == column => Some((column, true)),
+                    Some((sorted, true)) if sorted == column => None,
+                    _ => Some((column, false)),
+                };
+                self.order = None;
+                self.search.matches = None;
+            }
+            Some(TableAction::ToggleFrozen(column)) => {
+                match self.frozen.iter().position(|f| f == &column) {
+                    Some(i) => {
+                        self.frozen.remove(i);
+                    }
+                    None => self.frozen.push(column),
+                }
+                self.selection = None;
+            }
+            Some(TableAction::Select { cell, extend }) => {
+                self.selection = match self.selection.take() {
+                    Some(selection) if extend => Some(CellSelection {
+                        anchor: selection.anchor,
+                        cursor: cell,
+                    }),
+                    _ => Some(CellSelection {
+                        anchor: cell,
+                        cursor: cell,
+                    }),
+                };
+            }
+            Some(TableAction::StartDrag(cell)) => {
+                self.selection = Some(CellSelection {
+                    anchor: cell,
+                    cursor: cell,
+                });
+                self.dragging = true;
+            }
+            Some(TableAction::ToggleRow(row)) => {
+                let row = self.data_row(row);
+                if !self.selected_rows.remove(&row) {
+                    self.selected_rows.insert(row);
+                }
+            }
+            Some(TableAction::Format(column, format)) => match format {
+                Some(format) => {
+                    self.column_formats.insert(column, format);
+                }
+                None => {
+                    self.column_formats.remove(&column);
+                }
+            },
+            Some(TableAction::Filter(column, operation, value)) => {
+                self.filter = Some((column, operation, value))
+            }
+            Some(TableAction::Inspect(row)) => self.inspect = Some(row),
+            Some(TableAction::Stats(column)) => {
+                if let Ok(series) = df.column(&column) {
+                    self.stats.insert(column, ColumnStats::new(series));
+                    ui.ctx().request_repaint();
+                }
+            }
+            Some(TableAction::Edit(edit)) => {
+                self.editing = Some(edit);
+                self.edit_error = None;
+            }
+            Some(TableAction::CommitEdit) => self.edited = self.editing.take(),
+            Some(TableAction::CancelEdit) => self.editing = None,
+            None => {}
+        }
+        if !ui.input(|i| i.pointer.primary_down()) {
+            self.dragging = false;
+        }
+        let copy = ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)));
+        if copy && ui.memory(|m| m.focused().is_none()) {
+            match self.selected_text(df) {
+                Ok(Some(text)) => ui.ctx().copy_text(text),
+                Ok(None) => {}
+                Err(e) => log::error!("Could not copy the selected cells: {}", e),
+            }
+        }
+    }
+
+    /// The arrangement of the view kept across restarts.
+    pub fn layout(&self) -> TableLayout {
+        TableLayout {
+            page_size: self.page_size,
+            sort: self.sort.clone(),
+            hidden: self.hidden.clone(),
+            frozen: self.frozen.clone(),
+            widths: self.widths.clone(),
+        }
+    }
+
+    /// Restores a saved arrangement, leaving out the columns `df` doesn't have.
+    pub fn set_layout(&mut self, layout: &TableLayout, df: &DataFrame) {
+        let known = |column: &String| df.get_column_index(column).is_some();
+        self.page_size = layout.page_size;
+        self.sort = layout.sort.clone().filter(|(column, _)| known(column));
+        self.hidden = layout.hidden.iter().filter(|c| known(c)).cloned().collect();
+        self.frozen = layout.frozen.iter().filter(|c| known(c)).cloned().collect();
+        self.widths = layout.widths.clone();
+        self.order = None;
+    }
+
+    /// Forgets the cached row order and selections, to be called when the data changes.
+    pub fn invalidate(&mut self) {
+        self.order = None;
+        self.search.matches = None;
+        self.stats.clear();
+        self.selection = None;
+        self.selected_rows.clear();
+    }
+
+    /// The last edit committed in the view, as the DataFrame row, column and typed text.
+    pub fn take_edit(&mut self) -> Option<(usize, String, String)> {
+        let edit = self.edited.take()?;
+        Some((self.data_row(edit.row), edit.column, edit.text))
+    }
+
+    /// The filter picked from a cell, as column, operation and value.
+    pub fn take_filter(&mut self) -> Option<(String, FilterOps, String)> {
+        self.filter.take()
+    }
+
+    /// The selected rows with all their columns, in their DataFrame order.
+    pub fn selected(&self, df: &DataFrame) -> PolarsResult<DataFrame> {
+        let rows: Vec<IdxSize> = self.selected_rows.iter().map(|r| *r as IdxSize).collect();
+        df.take(&IdxCa::from_vec("rows", rows))
+    }
+
+    /// A cell value in the format of its column, styled by the matching rules. Nulls stand
+    /// out when highlighted.
+    fn show_value(&self, column: &Series, idx: usize, ui: &mut egui::Ui) {
+        let Ok(value) = column.get(idx) else {
+            return;
+        };
+        let mut text = match value {
+            AnyValue::Null if self.highlight_nulls => {
+                let fill = ui.visuals().warn_fg_color.gamma_multiply(0.15);
+                ui.painter().rect_filled(ui.max_rect(), 0.0, fill);
+                RichText::new("null").weak().italics()
+            }
+            _ => RichText::new(self.value_text(column.name(), &value)),
+        };
+        if !self.search.text.is_empty() && self.search.includes(column.name()) {
+            let found = match &value {
+                AnyValue::String(v) => self.search.found_in(v),
+                v => self.search.found_in(&v.to_string()),
+            };
+            if found {
+                let fill = Color32::from_rgba_unmultiplied(255, 210, 0, 70);
+                ui.painter().rect_filled(ui.max_rect(), 0.0, fill);
+            }
+        }
+        for rule in &self.rules {
+            if rule.matches(column.name(), &value) {
+                text = rule.apply(text, ui);
+            }
+        }
+        // clipped values end with an ellipsis and show in full on hover.
+        ui.add(egui::Label::new(text).truncate());
+    }
+
+    /// A value as text, in the format of its column.
+    fn value_text(&self, column: &str, value: &AnyValue<'_>) -> String {
+        self.column_format(column)
+            .format(value)
+            .unwrap_or_else(|| value.to_string().replace('"', ""))
+    }
+
+    fn column_format(&self, column: &str) -> &ValueFormat {
+        self.column_formats
+            .get(column)
+            .unwrap_or(&self.value_format)
+    }
+
+    /// Row of the DataFrame shown at a row of the (sorted) view.
+    fn data_row(&self, row: usize) -> usize {
+        match self.order.as_ref().and_then(|order| order.get(row)) {
+            Some(row) => row as usize,
+            None => row,
+        }
+    }
+
+    /// The selected cells as tab separated lines, ready to paste into a spreadsheet.
+    fn selected_text(&self, df: &DataFrame) -> PolarsResult<Option<String>> {
+        let Some(selection) = &self.selection else {
+            return Ok(None);
+        };
+        let (rows, columns) = selection.ranges();
+        let page = self.rows(df, rows)?;
+        let names = self.shown_columns(&page);
+        let Some(names) = names.get(columns) else {
+            return Ok(None);
+        };
+        let selected = page.select(names.iter().copied())?;
+        let lines: Vec<String> = (0..selected.height())
+            .map(|idx| {
+                selected
+                    .get_columns()
+                    .iter()
+                    .map(|column| cell_text(column, idx))
+                    .collect::<Vec<_>>()
+                    .join("\t")
+            })
+            .collect();
+        Ok(Some(lines.join("\n")))
+    }
+
+    /// Columns of the column page in the order shown: the frozen ones, then a page of the
+    /// others.
+    fn shown_columns<'a>(&self, df: &'a DataFrame) -> Vec<&'a str> {
+        let (frozen, rest) = self.column_order(df);
+        let start = (self.column_page * self.columns_per_page).min(rest.len());
+        let end = (start + self.columns_per_page).min(rest.len());
+        frozen
+            .into_iter()
+            .chain(rest[start..end].iter().copied())
+            .collect()
+    }
+
+    /// Frozen and other column names, in the order the view shows them.
+    fn column_order<'a>(&self, df: &'a DataFrame) -> (Vec<&'a str>, Vec<&'a str>) {
+        df.get_column_names()
+            .into_iter()
+            .partition(|c| self.frozen.iter().any(|f| f == c))
+    }
+
+    /// Rows of the view in the given range, following the sort order and without the hidden
+    /// columns.
+    pub fn rows(&self, df: &DataFrame, rows: Range<usize>) -> PolarsResult<DataFrame> {
+        let columns = df
+            .get_column_names()
+            .into_iter()
+            .filter(|c| !self.hidden.iter().any(|h| h == c));
+        let df = df.select(columns)?;
+        let length = rows.end.saturating_sub(rows.start);
+        match &self.order {
+            Some(order) => df.take(&order.slice(rows.start as i64, length)),
+            None => Ok(df.slice(rows.start as i64, length)),
+        }
+    }
+
+    fn update_order(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        match &self.sort {
+            Some((column, descending)) if self.order.is_none() => {
+                let options = SortOptions::default()
+                    .with_order_descending(*descending)
+                    .with_nulls_last(true);
+                self.order = Some(df.column(column)?.arg_sort(options));
+            }
+            None => self.order = None,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Record inspector: the columns of a row with their types and values, one per line.
+    fn show_record(&mut self, df: &DataFrame, row: usize, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.add_enabled(row > 0, Button::new("◀")).clicked() {
+                self.inspect = Some(row - 1);
+            }
+            if ui
+                .add_enabled(row + 1 < df.height(), Button::new("▶"))
+                .clicked()
+            {
+                self.inspect = Some(row + 1);
+            }
+        });
+        let record = match self.rows(df, row..row + 1) {
+            Ok(record) if record.height() == 1 => record,
+            _ => return,
+        };
+        egui::ScrollArea::vertical()
+            .max_height(500.0)
+            .show(ui, |ui| {
+                egui::Grid::new("record")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for column in record.get_columns() {
+                            ui.strong(column.name());
+                            ui.label(RichText::new(column.dtype().to_string()).small().weak());
+                            match column.get(0) {
+                                Ok(AnyValue::Null) => ui.weak("null"),
+                                Ok(value) => ui.label(self.value_text(column.name(), &value)),
+                                Err(_) => ui.label(""),
+                            };
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Search box with the chosen column and buttons jumping between the matching rows.
+    fn show_search(&mut self, df: &DataFrame, ui: &mut egui::Ui) {
+        let response = ui.add(TextEdit::singleline(&mut self.search.text).hint_text("🔍 Search"));
+        let column = match self.search.column.is_empty() {
+            true => "Any column",
+            false => self.search.column.as_str(),
+        };
+        let mut changed = response.changed();
+        ComboBox::from_id_source("search_column")
+            .selected_text(column.to_string())
+            .show_ui(ui, |ui| {
+                changed |= ui
+                    .selectable_value(&mut self.search.column, String::new(), "Any column")
+                    .changed();
+                for column in df.get_column_names() {
+                    changed |= ui
+                        .selectable_value(&mut self.search.column, column.to_string(), column)
+                        .changed();
+                }
+            });
+        if changed {
+            self.search.matches = None;
+        }
+        if self.search.text.is_empty() {
+            return;
+        }
+        let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        let previous = ui.button("◀").clicked();
+        let next = ui.button("▶").clicked() || enter;
+        if (previous || next) && self.search.matches.is_none() {
+            match self.search_rows(df) {
+                Ok(matches) => self.search.matches = Some(matches),
+                Err(e) => log::error!("Could not search for {}: {}", self.search.text, e),
+            }
+            // the first jump goes to the first match.
+            self.search.current = None;
+        }
+        let Some(matches) = &self.search.matches else {
+            return;
+        };
+        if matches.is_empty() {
+            ui.label("No matches");
+            return;
+        }
+        if previous || next {
+            let current = match (self.search.current, next) {
+                (None, _) => 0,
+                (Some(i), true) => (i + 1) % matches.len(),
+                (Some(i), false) => (i + matches.len() - 1) % matches.len(),
+            };
+            let row = matches[current];
+            self.search.current = Some(current);
+            self.page = row / self.page_size;
+            self.search.scroll_to = Some(row % self.page_size);
+            if enter {
+                response.request_focus();
+            }
+        }
+        match self.search.current {
+            Some(i) => ui.label(format!("{} of {}", i + 1, matches.len())),
+            None => ui.label(format!("{} matches", matches.len())),
+        };
+    }
+
+    /// Rows of the view with a value containing the search text, ignoring case.
+    fn search_rows(&self, df: &DataFrame) -> PolarsResult<Vec<usize>> {
+        let mut found = vec![false; df.height()];
+        for column in df.get_columns() {
+            if !self.search.includes(column.name())
+                || self.hidden.iter().any(|h| h == column.name())
+            {
+                continue;
+            }
+            let text = column.cast(&DataType::String)?;
+            for (row, value) in text.str()?.into_iter().enumerate() {
+                if value.is_some_and(|v| self.search.found_in(v)) {
+                    found[row] = true;
+                }
+            }
+        }
+        Ok(match &self.order {
+            Some(order) => order
+                .into_no_null_iter()
+                .enumerate()
+                .filter(|(_, row)| found[*row as usize])
+                .map(|(i, _)| i)
+                .collect(),
+            None => (0..found.len()).filter(|row| found[*row]).collect(),
+        })
+    }
+
+    fn show_pager(&mut self, height: usize, ui: &mut egui::Ui) {
+        let pages = height.div_ceil(self.page_size).max(1);
+        self.page = self.page.min(pages - 1);
+        if ui.add_enabled(self.page > 0, Button::new("◀")).clicked() {
+            self.page -= 1;
+        }
+        let mut page = self.page + 1;
+        ui.add(DragValue::new(&mut page).range(1..=pages).prefix("Page "));
+        self.page = page - 1;
+        ui.label(format!("of {}", pages));
+        if ui
+            .add_enabled(self.page + 1 < pages, Button::new("▶"))
+            .clicked()
+        {
+            self.page += 1;
+        }
+        let first_row = self.page * self.page_size;
+        ComboBox::from_id_source("page_size")
+            .selected_text(format!("{} rows", self.page_size))
+            .show_ui(ui, |ui| {
+                for size in PAGE_SIZES {
+                    ui.selectable_value(&mut self.page_size, size, format!("{} rows", size));
+                }
+            });
+        // stay around the same rows when the page size changes.
+        self.page = first_row / self.page_size;
+    }
+
+    /// Buttons moving through the pages of columns, shown when they don't fit in one page.
+    fn show_column_pager(&mut self, df: &DataFrame, ui: &mut egui::Ui) {
+        let columns = df
+            .get_column_names()
+            .into_iter()
+            .filter(|c| !self.hidden.iter().chain(&self.frozen).any(|h| h == c))
+            .count();
+        let pages = columns.div_ceil(self.columns_per_page).max(1);
+        self.column_page = self.column_page.min(pages - 1);
+        if pages == 1 {
+            return;
+        }
+        let page = self.column_page;
+        if ui.add_enabled(page > 0, Button::new("◀")).clicked() {
+            self.column_page -= 1;
+        }
+        let start = page * self.columns_per_page;
+        let end = (start + self.columns_per_page).min(columns);
+        ui.label(format!("Columns {}-{} of {}", start + 1, end, columns));
+        if ui.add_enabled(page + 1 < pages, Button::new("▶")).clicked() {
+            self.column_page += 1;
+        }
+        ui.add(
+            DragValue::new(&mut self.columns_per_page)
+                .range(5..=500)
+                .suffix(" per page"),
+        );
+        if self.column_page != page {
+            // cell positions refer to the columns of the page.
+            self.selection = None;
+        }
+    }
+
+    /// Menu with a checkbox per column to show or hide it, filtered by a search box.
+    fn show_column_picker(&mut self, df: &DataFrame, ui: &mut egui::Ui) {
+        let label = match self.hidden.len() {
+            0 => String::from("Columns"),
+            n => format!("Columns ({} hidden)", n),
+        };
+        ui.menu_button(label, |ui| {
+            ui.add(TextEdit::singleline(&mut self.column_search).hint_text("Search"));
+            ui.horizontal(|ui| {
+                if ui.button("Show all").clicked() {
+                    self.hidden.clear();
+                }
+                if ui.button("Hide all").clicked() {
+                    self.hidden = df
+                        .get_column_names()
+                        .iter()
+                        .map(|c| c.to_string())
+                        .collect();
+                }
+            });
+            ui.separator();
+            let search = self.column_search.to_lowercase();
+            egui::ScrollArea::vertical()
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for column in df.get_column_names() {
+                        if !column.to_lowercase().contains(&search) {
+                            continue;
+                        }
+                        let mut shown = !self.hidden.iter().any(|h| h == column);
+                        if ui.checkbox(&mut shown, column).changed() {
+                            match shown {
+                                true => self.hidden.retain(|h| h != column),
+                                false => self.hidden.push(column.to_string()),
+                            }
+                        }
+                    }
+                });
+        });
+    }
+
+    /// Renders the rows of a page starting at row `offset` of the view. Frozen columns go in
+    /// a table of their own, left of a horizontally scrolling table with the other columns.
+    /// Returns the range of page rows that were visible and the user action, if any.
+    fn show_rows(
+        &self,
+        df: &DataFrame,
+        offset: usize,
+        frame: &mut FrameState,
+        ui: &mut egui::Ui,
+    ) -> (Range<usize>, Option<TableAction>) {
+        let (frozen, rest) = self.column_order(df);
+        let (Ok(frozen), Ok(rest)) = (df.select(frozen), df.select(rest)) else {
+            return self.show_table(df, offset, 0, true, frame, ui);
+        };
+        if frozen.width() == 0 {
+            return self.show_table(df, offset, 0, true, frame, ui);
+        }
+        // both tables scroll vertically together inside one scroll area.
+        egui::ScrollArea::vertical()
+            .show(ui, |ui| {
+                ui.horizontal_top(|ui| {
+                    let (visible, frozen_action) = ui
+                        .push_id("frozen_columns", |ui| {
+                            self.show_table(&frozen, offset, 0, false, frame, ui)
+                        })
+                        .inner;
+                    let (_, action) = egui::ScrollArea::horizontal()
+                        .show(ui, |ui| {
+                            self.show_table(&rest, offset, frozen.width(), false, frame, ui)
+                        })
+                        .inner;
+                    (visible, frozen_action.or(action))
+                })
+                .inner
+            })
+            .inner
+    }
+
+    /// A table with the columns of `df`, the first of them being column `first_column` of the
+    /// view. The leftmost table also gets the row numbers. Without `vscroll` the table relies
+    /// on an outer scroll area.
+    fn show_table(
+        &self,
+        df: &DataFrame,
+        offset: usize,
+        first_column: usize,
+        vscroll: bool,
+        frame: &mut FrameState,
+        ui: &mut egui::Ui,
+    ) -> (Range<usize>, Option<TableAction>) {
+        let columns = df.get_columns();
+        let row_numbers = first_column == 0;
+        let (selected_rows, selected_columns) = match &self.selection {
+            Some(selection) => selection.ranges(),
+            None => (0..0, 0..0),
+        };
+        let mut visible = df.height()..0;
+        let mut action = None;
+        // a table state per set of columns, so hiding or freezing a column doesn't hand its
+        // width over to the next one. Columns resized before start from their width.
+        ui.push_id(df.get_column_names(), |ui| {
+            let mut table = TableBuilder::new(ui)
+                .vscroll(vscroll)
+                .striped(true)
+                .resizable(true)
+                .sense(Sense::click_and_drag());
+            if let Some(row) = self.search.scroll_to {
+                table = table.scroll_to_row(row, Some(egui::Align::Center));
+            }
+            if row_numbers {
+                table = table.column(Column::auto());
+            }
+            for column in columns {
+                table = table.column(match frame.widths.get(column.name()) {
+                    Some(width) => Column::initial(*width).clip(true),
+                    None => Column::auto().clip(true),
+                });
+            }
+            table
+                .header(36.0, |mut header| {
+                    if row_numbers {
+                        header.col(|ui| {
+                            ui.label("Row");
+                        });
+                    }
+                    for column in columns {
+                        header.col(|ui| {
+                            let width = ui.max_rect().width();
+                            if !ui.is_sizing_pass() && width.is_finite() {
+                                frame.widths.insert(column.name().to_string(), width);
+                            }
+                            ui.vertical(|ui| {
+                                let name = column.name();
+                                let frozen = self.frozen.iter().any(|f| f == name);
+                                let arrow = match &self.sort {
+                                    Some((sorted, false)) if sorted == name => " ⏶",
+                                    Some((sorted, true)) if sorted == name => " ⏷",
+                                    _ => "",
+                                };
+                                let pin = if frozen { "📌 " } else { "" };
+                                let text =
+                                    RichText::new(format!("{}{}{}", pin, name, arrow)).heading();
+                                let response = ui
+                                    .add(egui::Label::new(text).sense(Sense::click()))
+                                    .on_hover_ui(|ui| {
+                                        match self.stats.get(name) {
+                                            Some(stats) => stats.show(ui),
+                                            None => {
+                                                ui.spinner();
+                                                action = Some(TableAction::Stats(name.to_string()));
+                                            }
+                                        }
+                                        ui.weak("Click to sort, right click for more");
+                                    });
+                                if response.clicked() {
+                                    action = Some(TableAction::Sort(name.to_string()));
+                                }
+                                response.context_menu(|ui| {
+                                    let label = if frozen { "Unfreeze" } else { "Freeze" };
+                                    if ui.button(label).clicked() {
+                                        action = Some(TableAction::ToggleFrozen(name.to_string()));
+                                        ui.close_menu();
+                                    }
+                                    let temporal = matches!(
+                                        column.dtype(),
+                                        DataType::Date | DataType::Datetime(_, None)
+                                    );
+                                    if column.dtype().is_numeric() || temporal {
+                                        let label = match temporal {
+                                            true => "Datetime format",
+                                            false => "Number format",
+                                        };
+                                        ui.menu_button(label, |ui| {
+                                            let mut format = self.column_format(name).clone();
+                                            let changed = match temporal {
+                                                true => format.show_datetime(ui),
+                                                false => format.show_number(ui),
+                                            };
+                                            if changed {
+                                                action = Some(TableAction::Format(
+                                                    name.to_string(),
+                                                    Some(format),
+                                                ));
+                                            }
+                                            ui.separator();
+                                            if ui.button("Use default").clicked() {
+                                                action = Some(TableAction::Format(
+                                                    name.to_string(),
+                                                    None,
+                                                ));
+                                                ui.close_menu();
+                                            }
+                                        });
+                                    }
+                                });
+                                ui.label(RichText::new(column.dtype().to_string()).small().weak());
+                            });
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(10.0, df.height(), |mut row| {
+                        let idx = row.index();
+                        visible.start = visible.start.min(idx);
+                        visible.end = visible.end.max(idx + 1);
+                        if row_numbers {
+                            row.col(|ui| {
+                                let data_row = self.data_row(offset + idx);
+                                let mut checked = self.selected_rows.contains(&data_row);
+                                if ui.checkbox(&mut checked, "").clicked() {
+                                    action = Some(TableAction::ToggleRow(offset + idx));
+                                }
+                                // the row of the data, which stays with the row when sorted.
+                                let label =
+                                    egui::Label::new(format!("{}", data_row)).sense(Sense::click());
+                                if ui.add(label).on_hover_text("Inspect row").clicked() {
+                                    action = Some(TableAction::Inspect(offset + idx));
+                                }
+                            });
+                        }
+                        for (i, column) in columns.iter().enumerate() {
+                            let cell = (offset + idx, first_column + i);
+                            let mut edit_action = None;
+                            let (rect, response) = row.col(|ui| {
+                                if selected_rows.contains(&cell.0)
+                                    && selected_columns.contains(&cell.1)
+                                {
+                                    let fill = ui.visuals().selection.bg_fill;
+                                    ui.painter().rect_filled(ui.max_rect(), 0.0, fill);
+                                }
+                                match &mut frame.editing {
+                                    Some(edit)
+                                        if edit.row == cell.0 && edit.column == column.name() =>
+                                    {
+                                        let response = ui.add(
+                                            TextEdit::singleline(&mut edit.text)
+                                                .desired_width(f32::INFINITY),
+                                        );
+                                        if !edit.focused {
+                                            response.request_focus();
+                                            edit.focused = true;
+                                        } else if response.lost_focus() {
+                                            let cancel =
+                                                ui.input(|i| i.key_pressed(egui::Key::Escape));
+                                            edit_action = Some(match cancel {
+                                                true => TableAction::CancelEdit,
+                                                false => TableAction::CommitEdit,
+                                            });
+                                        }
+                                    }
+                                    _ => self.show_value(column, idx, ui),
+                                }
+                            });
+                            if edit_action.is_some() {
+                                action = edit_action;
+                                continue;
+                            }
+                            let ctx = &response.ctx;
+                            let extend = ctx.input(|i| i.modifiers.shift);
+                            if self.edit_mode && response.double_clicked() {
+                                action = Some(TableAction::Edit(CellEdit {
+                                    row: cell.0,
+                                    column: column.name().to_string(),
+                                    text: cell_text(column, idx),
+                                    focused: false,
+                                }));
+                            } else if response.clicked() {
+                                action = Some(TableAction::Select { cell, extend });
+                            } else if response.drag_started() {
+                                action = Some(TableAction::StartDrag(cell));
+                            } else if self.dragging
+                                && ctx
+                                    .pointer_latest_pos()
+                                    .is_some_and(|pos| rect.contains(pos))
+                            {
+                                // dragging a rectangle over the cells.
+                                action = Some(TableAction::Select { cell, extend: true });
+                            }
+                            response.context_menu(|ui| {
+                                let Some((keep, exclude)) = value_filters(column, idx) else {
+                                    ui.label("No filters for this type");
+                                    return;
+                                };
+                                for (label, operation) in [
+                                    ("Filter rows equal to this value", keep),
+                                    ("Exclude this value", exclude),
+                                ] {
+                                    if ui.button(label).clicked() {
+                                        action = Some(TableAction::Filter(
+                                            column.name().to_string(),
+                                            operation,
+                                            cell_text(column, idx),
+                                        ));
+                                        ui.close_menu();
+                                    }
+                                }
+                            });
+                        }
+                    });
+                });
+        });
+        (visible, action)
+    }
+}
+
+/// Column arrangement of a data view, persisted with the app by container title.
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct TableLayout {
+    pub page_size: usize,
+    pub sort: Option<(String, bool)>,
+    pub hidden: Vec<String>,
+    pub frozen: Vec<String>,
+    pub widths: HashMap<String, f32>,
+}
+
+impl Default for TableLayout {
+    fn default() -> Self {
+        TableView::default().layout()
+    }
+}
+
+/// View state changed while the tables are drawn, moved out of the view for the frame.
+struct FrameState {
+    editing: Option<CellEdit>,
+    widths: HashMap<String, f32>,
+}
+
+/// A rectangle of cells, from the cell first clicked to the one the selection was extended
+/// to. Cells are (row, column) positions in the view.
+#[derive(Clone, Debug)]
+pub struct CellSelection {
+    pub anchor: (usize, usize),
+    pub cursor: (usize, usize),
+}
+
+impl CellSelection {
+    fn ranges(&self) -> (Range<usize>, Range<usize>) {
+        let (rows, columns) = (
+            (self.anchor.0, self.cursor.0),
+            (self.anchor.1, self.cursor.1),
+        );
+        (
+            rows.0.min(rows.1)..rows.0.max(rows.1) + 1,
+            columns.0.min(columns.1)..columns.0.max(columns.1) + 1,
+        )
+    }
+}
+
+/// What a click on the table asks for.
+enum TableAction {
+    Sort(String),
+    ToggleFrozen(String),
+    Select { cell: (usize, usize), extend: bool },
+    StartDrag((usize, usize)),
+    ToggleRow(usize),
+    Format(String, Option<ValueFormat>),
+    Stats(String),
+    Inspect(usize),
+    Filter(String, FilterOps, String),
+    Edit(CellEdit),
+    CommitEdit,
+    CancelEdit,
+}
+
+/// How numbers and dates are displayed in the view. The data itself is left as is.
+#[derive(Clone, Debug, Default)]
+pub struct ValueFormat {
+    /// Fixed number of decimal places, full precision when unset.
+    pub decimals: Option<usize>,
+    pub thousands: bool,
+    pub scientific: bool,
+    /// Multiplies by 100 and adds a percent sign.
+    pub percent: bool,
+    /// strftime format for dates and datetimes, the default display when empty.
+    pub datetime: String,
+}
+
+impl ValueFormat {
+    /// The formatted value, or None for values left to their default display.
+    fn format(&self, value: &AnyValue<'_>) -> Option<String> {
+        match value {
+            AnyValue::Date(_) | AnyValue::Datetime(..) => self.format_datetime(value),
+            _ => self.format_number(value),
+        }
+    }
+
+    fn format_number(&self, value: &AnyValue<'_>) -> Option<String> {
+        let default = self.decimals.is_none() && !self.thousands && !self.scientific;
+        if (default && !self.percent) || !value.dtype().is_numeric() {
+            return None;
+        }
+        let mut number = value.extract::<f64>()?;
+        let suffix = match self.percent {
+            true => {
+                number *= 100.0;
+                "%"
+            }
+            false => "",
+        };
+        let text = match (self.scientific, self.decimals) {
+            (true, Some(decimals)) => format!("{:.*e}", decimals, number),
+            (true, None) => format!("{:e}", number),
+            (false, Some(decimals)) => format!("{:.*}", decimals, number),
+            (false, None) if self.percent => number.to_string(),
+            (false, None) => match value {
+                AnyValue::Float32(v) => v.to_string(),
+                AnyValue::Float64(v) => v.to_string(),
+                _ => value.to_string(),
+            },
+        };
+        match self.thousands && !self.scientific {
+            true => Some(format!("{}{}", group_thousands(&text), suffix)),
+            false => Some(format!("{}{}", text, suffix)),
+        }
+    }
+
+    /// Datetimes with a time zone keep the default display, which shows the zone.
+    fn format_datetime(&self, value: &AnyValue<'_>) -> Option<String> {
+        if self.datetime.is_empty() {
+            return None;
+        }
+        let datetime = match value {
+            AnyValue::Date(days) => date32_to_datetime_opt(*days)?,
+            AnyValue::Datetime(v, TimeUnit::Nanoseconds, None) => timestamp_ns_to_datetime_opt(*v)?,
+            AnyValue::Datetime(v, TimeUnit::Microseconds, None) => {
+                timestamp_us_to_datetime_opt(*v)?
+            }
+            AnyValue::Datetime(v, TimeUnit::Milliseconds, None) => {
+                timestamp_ms_to_datetime_opt(*v)?
+            }
+            _ => return None,
+        };
+        // an invalid format is an error here rather than a panic with to_string.
+        let mut text = String::new();
+        write!(text, "{}", datetime.format(&self.datetime)).ok()?;
+        Some(text)
+    }
+
+    /// Number format options, returning whether any of them changed.
+    fn show_number(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            let mut fixed = self.decimals.is_some();
+            if ui.checkbox(&mut fixed, "Decimals").changed() {
+                self.decimals = fixed.then_some(2);
+                changed = true;
+            }
+            if let Some(decimals) = &mut self.decimals {
+                changed |= ui.add(DragValue::new(decimals).range(0..=12)).changed();
+            }
+        });
+        changed |= ui
+            .checkbox(&mut self.thousands, "Thousands separator")
+            .changed();
+        changed |= ui
+            .checkbox(&mut self.scientific, "Scientific notation")
+            .changed();
+        changed |= ui.checkbox(&mut self.percent, "Percentage").changed();
+        changed
+    }
+
+    /// Datetime format field, returning whether it changed.
+    fn show_datetime(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.add(TextEdit::singleline(&mut self.datetime).hint_text("%Y-%m-%d %H:%M:%S"))
+            .on_hover_text("strftime format, e.g. %d/%m/%Y. Leave empty for the default.")
+            .changed()
+    }
+}
+
+/// Inserts a comma between every three digits of the integer part of a number.
+fn group_thousands(number: &str) -> String {
+    let (sign, number) = match number.strip_prefix('-') {
+        Some(number) => ("-", number),
+        None => ("", number),
+    };
+    let (integer, fraction) = match number.find('.') {
+        Some(i) => number.split_at(i),
+        None => (number, ""),
+    };
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    format!("{}{}{}", sign, grouped, fraction)
+}
+
+/// Quick statistics of a column, shown when hovering its header.
+#[derive(Clone, Debug)]
+struct ColumnStats {
+    min: String,
+    max: String,
+    mean: Option<f64>,
+    nulls: usize,
+    distinct: Option<usize>,
+}
+
+impl ColumnStats {
+    fn new(column: &Series) -> Self {
+        let text = |value: PolarsResult<Scalar>| match value {
+            Ok(value) => value.value().to_string().replace('"', ""),
+            Err(_) => String::new(),
+        };
+        Self {
+            min: text(column.min_reduce()),
+            max: text(column.max_reduce()),
+            mean: column.mean(),
+            nulls: column.null_count(),
+            distinct: column.n_unique().ok(),
+        }
+    }
+
+    fn show(&self, ui: &mut egui::Ui) {
+        egui::Grid::new("column_stats")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Min:");
+                ui.label(&self.min);
+                ui.end_row();
+                ui.label("Max:");
+                ui.label(&self.max);
+                ui.end_row();
+                if let Some(mean) = self.mean {
+                    ui.label("Mean:");
+                    ui.label(format!("{:.4}", mean));
+                    ui.end_row();
+                }
+                ui.label("Nulls:");
+                ui.label(self.nulls.to_string());
+                ui.end_row();
+                if let Some(distinct) = self.distinct {
+                    ui.label("Distinct:");
+                    ui.label(distinct.to_string());
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+/// Text searched for in the data view and the rows of the view where it was found.
+#[derive(Clone, Debug, Default)]
+pub struct ViewSearch {
+    pub text: String,
+    /// Column searched in, any column when empty.
+    pub column: String,
+    matches: Option<Vec<usize>>,
+    current: Option<usize>,
+    /// Row of the page to scroll to in the next frame.
+    scroll_to: Option<usize>,
+}
+
+impl ViewSearch {
+    fn includes(&self, column: &str) -> bool {
+        self.column.is_empty() || self.column == column
+    }
+
+    fn found_in(&self, value: &str) -> bool {
+        value.to_lowercase().contains(&self.text.to_lowercase())
+    }
+}
+
+/// A cell being edited, by its row of the view and its column.
+#[derive(Clone, Debug)]
+struct CellEdit {
+    row: usize,
+    column: String,
+    text: String,
+    /// Whether the text field got the keyboard focus after opening.
+    focused: bool,
+}
+
+/// Filter operations keeping and excluding the rows equal to a value of the column.
+fn value_filters(column: &Series, idx: usize) -> Option<(FilterOps, FilterOps)> {
+    match column.get(idx).ok()? {
+        AnyValue::Null => Some((FilterOps::IsNull, FilterOps::IsNotNull)),
+        _ if column.dtype().is_numeric() => Some((FilterOps::EqualNum, FilterOps::NotEqualNum)),
+        AnyValue::String(_) => Some((FilterOps::EqualStr, FilterOps::NotEqualStr)),
+        _ => None,
+    }
+}
+
+/// A value as plain text: strings without quotes and nulls empty.
+fn cell_text(column: &Series, idx: usize) -> String {
+    match column.get(idx) {
+        Ok(AnyValue::Null) | Err(_) => String::new(),
+        Ok(AnyValue::String(value)) => value.to_string(),
+        Ok(value) => value.to_string(),
+    }
+}
+
+/// Parses the text typed in a cell to a single value of the given type. The text goes through
+/// the CSV reader so it is parsed the same way as imported files. Empty text is null.
+pub fn parse_value(text: &str, dtype: &DataType) -> PolarsResult<Series> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Ok(Series::full_null("value", 1, dtype));
+    }
+    let mut buffer = Vec::new();
+    CsvWriter::new(&mut buffer).finish(&mut df!("value" => [text])?)?;
+    let schema = Schema::from_iter([Field::new("value", dtype.clone())]);
+    let df = CsvReadOptions::default()
+        .with_has_header(true)
+        .with_schema_overwrite(Some(Arc::new(schema)))
+        .into_reader_with_file_handle(Cursor::new(buffer))
+        .finish();
+    match df.as_ref().map(|df| df.get_columns()) {
+        Ok([value]) if value.len() == 1 && value.null_count() == 0 => Ok(value.clone()),
+        _ => polars_bail!(ComputeError: "could not parse '{}' as {}", text, dtype),
+    }
+}
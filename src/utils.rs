@@ -1,4 +1,5 @@
 use crate::container::*;
+use egui::RichText;
 use egui_extras::{Column, TableBuilder};
 use polars::prelude::*;
 use std::collections::HashMap;
@@ -13,13 +14,16 @@ pub fn display_dataframe(df: &DataFrame, ui: &mut egui::Ui) {
         .columns(Column::auto().clip(true), nr_cols)
         .striped(true)
         .resizable(true)
-        .header(20.0, |mut header| {
+        .header(36.0, |mut header| {
             header.col(|ui| {
-                ui.label(format!("{}", "Row"));
+                ui.label("Row");
             });
-            for head in cols {
+            for column in df.get_columns() {
                 header.col(|ui| {
-                    ui.heading(format!("{}", head));
+                    ui.vertical(|ui| {
+                        ui.heading(column.name());
+                        ui.label(RichText::new(column.dtype().to_string()).small().weak());
+                    });
                 });
             }
         })
@@ -33,7 +37,8 @@ pub fn display_dataframe(df: &DataFrame, ui: &mut egui::Ui) {
                     row.col(|ui| {
                         if let Ok(column) = &df.column(col) {
                             if let Ok(value) = column.get(idx) {
-                                ui.label(format!("{}", value).replace('"', ""));
+                                let text = format!("{}", value).replace('"', "");
+                                ui.add(egui::Label::new(text).truncate());
                             }
                         }
                     });
@@ -53,3 +58,131 @@ pub fn get_container<'a>(
     }
     None
 }
+
+/// Summary statistics of every column, one row per statistic. Numeric columns stay numeric,
+/// other columns only get the count and min/max as text.
+pub fn describe_dataframe(df: &DataFrame) -> PolarsResult<DataFrame> {
+    let mut columns = vec![Series::new(
+        "statistic",
+        ["count", "null_count", "mean", "std", "min", "median", "max"],
+    )];
+    for series in df.get_columns() {
+        let count = (series.len() - series.null_count()) as f64;
+        let nulls = series.null_count() as f64;
+        if series.dtype().is_numeric() {
+            let values = series.cast(&DataType::Float64)?;
+            let values = values.f64()?;
+            columns.push(Series::new(
+                series.name(),
+                [
+                    Some(count),
+                    Some(nulls),
+                    values.mean(),
+                    values.std(1),
+                    values.min(),
+                    values.median(),
+                    values.max(),
+                ],
+            ));
+        } else {
+            let text = |value: PolarsResult<Scalar>| {
+                value
+                    .ok()
+                    .filter(|v| !v.value().is_null())
+                    .map(|v| v.value().to_string())
+            };
+            columns.push(Series::new(
+                series.name(),
+                [
+                    Some(count.to_string()),
+                    Some(nulls.to_string()),
+                    None,
+                    None,
+                    text(series.min_reduce()),
+                    None,
+                    text(series.max_reduce()),
+                ],
+            ));
+        }
+    }
+    DataFrame::new(columns)
+}
+
+/// A checkbox per column, in a scroll area, toggling the column in `selected`.
+pub fn column_checkboxes(
+    id: &str,
+    columns: &[String],
+    selected: &mut Vec<String>,
+    ui: &mut egui::Ui,
+) {
+    egui::ScrollArea::vertical()
+        .id_source(id)
+        .max_height(200.0)
+        .show(ui, |ui| {
+            for col in columns {
+                let mut checked = selected.contains(col);
+                if ui.checkbox(&mut checked, col).changed() {
+                    match checked {
+                        true => selected.push(col.to_owned()),
+                        false => selected.retain(|c| c != col),
+                    }
+                }
+            }
+        });
+}
+
+/// The name typed in, or one made of the column and the function, e.g. `amount_sum`.
+pub fn column_name(name: &str, column: &str, func: &str) -> String {
+    match name.trim().is_empty() {
+        true => format!("{}_{}", column, func),
+        false => name.trim().to_string(),
+    }
+}
+
+/// Fills the nulls between values linearly. Integer columns are rounded back to their type
+/// instead of turning into floats.
+pub fn interpolate_linear(expr: Expr, dtype: &DataType) -> Expr {
+    let interpolated = expr.interpolate(InterpolationMethod::Linear);
+    match dtype.is_integer() {
+        true => interpolated.round(0).strict_cast(dtype.clone()),
+        false => interpolated,
+    }
+}
+
+/// Whether the text is a duration like "5m" or "1h30m": numbers each followed by a unit.
+pub fn is_duration(text: &str) -> bool {
+    const UNITS: [&str; 12] = [
+        "ns", "us", "ms", "s", "m", "h", "d", "w", "mo", "q", "y", "i",
+    ];
+    if text.is_empty() {
+        return false;
+    }
+    let mut rest = text;
+    while !rest.is_empty() {
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let letters = rest[digits..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .count();
+        if digits == 0 || !UNITS.contains(&&rest[digits..digits + letters]) {
+            return false;
+        }
+        rest = &rest[digits + letters..];
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        for text in ["5m", "1h30m", "2mo", "1d12h", "500ms", "3i"] {
+            assert!(is_duration(text), "{}", text);
+        }
+        for text in ["", "5", "m", "1.5h", "5 m", "5minutes", "h1", "-1h"] {
+            assert!(!is_duration(text), "{}", text);
+        }
+    }
+}
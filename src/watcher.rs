@@ -0,0 +1,51 @@
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Flags when a file changes on disk. The parent folder is watched rather than the file itself
+/// so files replaced by a rename (as most editors and pipelines do) are still detected.
+#[derive(Clone)]
+pub struct FileWatcher {
+    _watcher: Rc<RecommendedWatcher>,
+    changed: Arc<AtomicBool>,
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path, ctx: &egui::Context) -> notify::Result<Self> {
+        let changed = Arc::new(AtomicBool::new(false));
+        let file: PathBuf = path.canonicalize()?;
+        let folder = file.parent().unwrap_or(&file).to_path_buf();
+        let flag = Arc::clone(&changed);
+        let ctx = ctx.clone();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            if let Ok(event) = event {
+                let modified = event.kind.is_modify() || event.kind.is_create();
+                if modified && event.paths.iter().any(|p| p == &file) {
+                    flag.store(true, Ordering::Relaxed);
+                    ctx.request_repaint();
+                }
+            }
+        })?;
+        watcher.watch(&folder, RecursiveMode::NonRecursive)?;
+        Ok(Self {
+            _watcher: Rc::new(watcher),
+            changed,
+        })
+    }
+
+    pub fn changed(&self) -> bool {
+        self.changed.load(Ordering::Relaxed)
+    }
+
+    pub fn reset(&self) {
+        self.changed.store(false, Ordering::Relaxed);
+    }
+}
+
+impl std::fmt::Debug for FileWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FileWatcher")
+    }
+}
@@ -0,0 +1,366 @@
+use crate::utils::{column_name, is_duration};
+use polars::prelude::*;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum WindowFunc {
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    Count,
+    First,
+    Last,
+}
+
+impl WindowFunc {
+    pub const ALL: [WindowFunc; 8] = [
+        WindowFunc::Sum,
+        WindowFunc::Mean,
+        WindowFunc::Median,
+        WindowFunc::Min,
+        WindowFunc::Max,
+        WindowFunc::Count,
+        WindowFunc::First,
+        WindowFunc::Last,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WindowFunc::Sum => "sum",
+            WindowFunc::Mean => "mean",
+            WindowFunc::Median => "median",
+            WindowFunc::Min => "min",
+            WindowFunc::Max => "max",
+            WindowFunc::Count => "count",
+            WindowFunc::First => "first",
+            WindowFunc::Last => "last",
+        }
+    }
+
+    fn apply(&self, expr: Expr) -> Expr {
+        match self {
+            WindowFunc::Sum => expr.sum(),
+            WindowFunc::Mean => expr.mean(),
+            WindowFunc::Median => expr.median(),
+            WindowFunc::Min => expr.min(),
+            WindowFunc::Max => expr.max(),
+            WindowFunc::Count => expr.count(),
+            WindowFunc::First => expr.first(),
+            WindowFunc::Last => expr.last(),
+        }
+    }
+}
+
+/// The expression computed within each group of the partition columns, or over all the rows
+/// without any.
+fn over(expr: Expr, partition_by: &[String]) -> Expr {
+    match partition_by.is_empty() {
+        true => expr,
+        false => expr.over(partition_by.iter().map(|c| col(c)).collect::<Vec<_>>()),
+    }
+}
+
+/// A column with an aggregate of another column over a partition, e.g. the total amount of
+/// each customer on each of their rows.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameWindow {
+    pub column: String,
+    pub func: WindowFunc,
+    pub partition_by: Vec<String>,
+    /// Name of the new column, `{column}_{func}` if empty.
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameWindow {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            func: WindowFunc::Sum,
+            partition_by: Vec::new(),
+            name: String::default(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameWindow {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to aggregate");
+        }
+        let name = column_name(&self.name, &self.column, self.func.label());
+        let expr = over(self.func.apply(col(&self.column)), &self.partition_by);
+        df.with_column(expr.alias(&name)).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RollingFunc {
+    Mean,
+    Sum,
+    Min,
+    Max,
+    Std,
+}
+
+impl RollingFunc {
+    pub const ALL: [RollingFunc; 5] = [
+        RollingFunc::Mean,
+        RollingFunc::Sum,
+        RollingFunc::Min,
+        RollingFunc::Max,
+        RollingFunc::Std,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RollingFunc::Mean => "mean",
+            RollingFunc::Sum => "sum",
+            RollingFunc::Min => "min",
+            RollingFunc::Max => "max",
+            RollingFunc::Std => "std",
+        }
+    }
+}
+
+/// A column with an aggregate of the last rows of another column, e.g. a 7 day moving average.
+/// The window is a number of rows, or a duration back from the date of each row.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameRolling {
+    pub column: String,
+    pub func: RollingFunc,
+    /// Size the window by a duration over the `by` column instead of a number of rows.
+    pub by_time: bool,
+    pub rows: usize,
+    pub by: String,
+    pub duration: String,
+    /// Name of the new column, `{column}_rolling_{func}` if empty.
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameRolling {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            func: RollingFunc::Mean,
+            by_time: false,
+            rows: 3,
+            by: String::default(),
+            duration: String::from("7d"),
+            name: String::default(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameRolling {
+    pub fn apply(&self, mut df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to aggregate");
+        }
+        let name = column_name(
+            &self.name,
+            &self.column,
+            &format!("rolling_{}", self.func.label()),
+        );
+        let value = col(&self.column);
+        let expr = match self.by_time {
+            true => {
+                let dtype = df.schema()?.try_get(&self.by)?.clone();
+                if !dtype.is_temporal() {
+                    polars_bail!(ComputeError: "'{}' is {}, not a date or time", self.by, dtype);
+                }
+                let duration = self.duration.trim();
+                if !is_duration(duration) {
+                    polars_bail!(ComputeError: "'{}' is not a duration like 7d or 12h", duration);
+                }
+                let options = RollingOptionsDynamicWindow {
+                    window_size: Duration::parse(duration),
+                    min_periods: 1,
+                    closed_window: ClosedWindow::Right,
+                    fn_params: None,
+                };
+                let by = col(&self.by);
+                match self.func {
+                    RollingFunc::Mean => value.rolling_mean_by(by, options),
+                    RollingFunc::Sum => value.rolling_sum_by(by, options),
+                    RollingFunc::Min => value.rolling_min_by(by, options),
+                    RollingFunc::Max => value.rolling_max_by(by, options),
+                    RollingFunc::Std => value.rolling_std_by(by, options),
+                }
+            }
+            false => {
+                if self.rows == 0 {
+                    polars_bail!(ComputeError: "the window needs at least one row");
+                }
+                let options = RollingOptionsFixedWindow {
+                    window_size: self.rows,
+                    min_periods: self.rows,
+                    ..Default::default()
+                };
+                match self.func {
+                    RollingFunc::Mean => value.rolling_mean(options),
+                    RollingFunc::Sum => value.rolling_sum(options),
+                    RollingFunc::Min => value.rolling_min(options),
+                    RollingFunc::Max => value.rolling_max(options),
+                    RollingFunc::Std => value.rolling_std(options),
+                }
+            }
+        };
+        df.with_column(expr.alias(&name)).collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangeOp {
+    CumSum,
+    Diff,
+    PctChange,
+    Shift,
+}
+
+impl ChangeOp {
+    pub const ALL: [ChangeOp; 4] = [
+        ChangeOp::CumSum,
+        ChangeOp::Diff,
+        ChangeOp::PctChange,
+        ChangeOp::Shift,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeOp::CumSum => "cum_sum",
+            ChangeOp::Diff => "diff",
+            ChangeOp::PctChange => "pct_change",
+            ChangeOp::Shift => "shift",
+        }
+    }
+}
+
+/// A column with the running total of another column, its change from `periods` rows before,
+/// or its value `periods` rows before or after, within each group of the partition columns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameChange {
+    pub column: String,
+    pub op: ChangeOp,
+    /// Rows back the change is computed from, or the rows to shift by. Not used by the
+    /// running total.
+    pub periods: i64,
+    /// Shift to the value `periods` rows after instead of before, leaving nulls at the end.
+    pub lead: bool,
+    pub partition_by: Vec<String>,
+    /// Name of the new column, the default name if empty.
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameChange {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            op: ChangeOp::CumSum,
+            periods: 1,
+            lead: false,
+            partition_by: Vec::new(),
+            name: String::default(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameChange {
+    /// Added to the column for the name of the new column, e.g. `amount_diff` or `amount_lag`.
+    fn suffix(&self) -> &'static str {
+        match (self.op, self.lead) {
+            (ChangeOp::Shift, true) => "lead",
+            (ChangeOp::Shift, false) => "lag",
+            (op, _) => op.label(),
+        }
+    }
+
+    /// Name of the new column when none is typed in.
+    pub fn default_name(&self) -> String {
+        column_name("", &self.column, self.suffix())
+    }
+
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to compute from");
+        }
+        let name = column_name(&self.name, &self.column, self.suffix());
+        let value = col(&self.column);
+        let expr = match self.op {
+            ChangeOp::CumSum => value.cum_sum(false),
+            ChangeOp::Diff => value.diff(self.periods, Default::default()),
+            ChangeOp::PctChange => value.pct_change(lit(self.periods)),
+            ChangeOp::Shift => match self.lead {
+                true => value.shift(lit(-self.periods)),
+                false => value.shift(lit(self.periods)),
+            },
+        };
+        df.with_column(over(expr, &self.partition_by).alias(&name))
+            .collect()
+    }
+}
+
+/// Ranking methods offered, with their labels. Ties share the rank with all but Ordinal.
+pub const RANK_METHODS: [(RankMethod, &str); 5] = [
+    (RankMethod::Dense, "Dense"),
+    (RankMethod::Ordinal, "Ordinal"),
+    (RankMethod::Min, "Min"),
+    (RankMethod::Max, "Max"),
+    (RankMethod::Average, "Average"),
+];
+
+/// A column with the rank of the values of another column, within each group of the partition
+/// columns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DataFrameRank {
+    pub column: String,
+    pub method: RankMethod,
+    pub descending: bool,
+    pub partition_by: Vec<String>,
+    /// Name of the new column, `{column}_rank` if empty.
+    pub name: String,
+    pub inplace: bool,
+    pub error: Option<String>,
+}
+
+impl Default for DataFrameRank {
+    fn default() -> Self {
+        Self {
+            column: String::default(),
+            method: RankMethod::Dense,
+            descending: false,
+            partition_by: Vec::new(),
+            name: String::default(),
+            inplace: false,
+            error: None,
+        }
+    }
+}
+
+impl DataFrameRank {
+    pub fn apply(&self, df: LazyFrame) -> PolarsResult<DataFrame> {
+        if self.column.is_empty() {
+            polars_bail!(ComputeError: "pick the column to rank");
+        }
+        let name = column_name(&self.name, &self.column, "rank");
+        let options = RankOptions {
+            method: self.method,
+            descending: self.descending,
+        };
+        let expr = col(&self.column).rank(options, None);
+        df.with_column(over(expr, &self.partition_by).alias(&name))
+            .collect()
+    }
+}